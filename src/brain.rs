@@ -0,0 +1,14 @@
+//! The brain's home screen, where programs are picked and started.
+
+use serde::{Deserialize, Serialize};
+
+/// A program installed in one of the brain's program slots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ProgramSlot {
+    /// The slot number, from 1 to 8.
+    pub slot: u8,
+    pub name: String,
+    pub description: Option<String>,
+    /// The file name of the program's icon on the brain, such as `USER029x.bmp`.
+    pub icon: Option<String>,
+}
@@ -0,0 +1,131 @@
+//! Merging redundant events so that slow frontends can process them in batches.
+
+use std::collections::HashMap;
+
+use crate::{
+    devices::{Port, SmartPort},
+    geometry::Rect,
+    Event,
+};
+
+/// Removes events from a batch that have no effect on the final state.
+///
+/// The following events are dropped:
+///
+/// - A `DeviceUpdate` or `DeviceLed` followed by another for the same port.
+/// - A `Battery`, `RobotPose`, or `RobotState` followed by another of the same kind.
+/// - A `ScreenDraw`, `ScreenScroll`, or `ScreenClear` whose clip region is completely covered
+///   by a later `ScreenClear` before the next `ScreenRender` or `ScreenDoubleBufferMode`.
+///
+/// All other events, and the relative order of the events that remain, are preserved.
+pub fn coalesce(events: impl IntoIterator<Item = Event>) -> Vec<Event> {
+    let mut output: Vec<Option<Event>> = Vec::new();
+    let mut device_updates: HashMap<Port, usize> = HashMap::new();
+    let mut device_leds: HashMap<SmartPort, usize> = HashMap::new();
+    let mut latest: HashMap<&'static str, usize> = HashMap::new();
+    let mut screen_ops: Vec<(usize, Rect)> = Vec::new();
+
+    for event in events {
+        let index = output.len();
+        let replaced = match &event {
+            Event::DeviceUpdate { port, .. } => device_updates.insert(*port, index),
+            Event::DeviceLed { port, .. } => device_leds.insert(*port, index),
+            Event::Battery(_) | Event::RobotPose { .. } | Event::RobotState(_) => {
+                latest.insert(event.kind(), index)
+            }
+            Event::ScreenClear { clip_region, .. } => {
+                screen_ops.retain(|(op, region)| {
+                    let covered = clip_region.contains_rect(region);
+                    if covered {
+                        output[*op] = None;
+                    }
+                    !covered
+                });
+                screen_ops.push((index, *clip_region));
+                None
+            }
+            Event::ScreenDraw { clip_region, .. } | Event::ScreenScroll { clip_region, .. } => {
+                screen_ops.push((index, *clip_region));
+                None
+            }
+            Event::ScreenRender | Event::ScreenDoubleBufferMode { .. } => {
+                screen_ops.clear();
+                None
+            }
+            _ => None,
+        };
+        if let Some(replaced) = replaced {
+            output[replaced] = None;
+        }
+        output.push(Some(event));
+    }
+
+    output.into_iter().flatten().collect()
+}
+
+/// Merges repeated log messages into a single [`Event::Log`] whose `count` is the total
+/// number of times the message was logged.
+///
+/// A log message is merged into the previous log message in the batch if every field other
+/// than `count` is identical, even if other kinds of events were sent in between. This keeps a
+/// program that logs in a tight loop from flooding the transport and the frontend's log view.
+///
+/// The merged message stays where the first repetition was, so later repetitions are moved
+/// ahead of any other events sent between them. The order of log messages relative to each
+/// other, and of all other events, is preserved.
+pub fn collapse_logs(events: impl IntoIterator<Item = Event>) -> Vec<Event> {
+    let mut output: Vec<Event> = Vec::new();
+    let mut last_log: Option<usize> = None;
+
+    for event in events {
+        if let Event::Log { count, .. } = &event {
+            if let Some(Event::Log {
+                count: last_count, ..
+            }) = last_log
+                .map(|index| &mut output[index])
+                .filter(|last| same_log(last, &event))
+            {
+                *last_count = last_count.saturating_add(*count);
+                continue;
+            }
+            last_log = Some(output.len());
+        }
+        output.push(event);
+    }
+
+    output
+}
+
+/// Whether two log events are identical apart from their counts.
+fn same_log(a: &Event, b: &Event) -> bool {
+    match (a, b) {
+        (
+            Event::Log {
+                level,
+                message,
+                source,
+                category,
+                color,
+                location,
+                count: _,
+            },
+            Event::Log {
+                level: other_level,
+                message: other_message,
+                source: other_source,
+                category: other_category,
+                color: other_color,
+                location: other_location,
+                count: _,
+            },
+        ) => {
+            level == other_level
+                && message == other_message
+                && source == other_source
+                && category == other_category
+                && color == other_color
+                && location == other_location
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,880 @@
+//! Reading and writing protocol messages as newline-delimited JSON.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    extensions, session::SessionInfo, v1, v2, Command, Event, HandshakeRejection, PROTOCOL_VERSION,
+};
+
+/// An error that occurred while reading or writing a message.
+#[derive(Debug)]
+pub enum CodecError {
+    /// The underlying stream failed.
+    Io(io::Error),
+    /// A message could not be serialized or a line could not be parsed as a message.
+    Json(serde_json::Error),
+    /// A line was longer than [`Limits::max_message_size`] bytes. The line was skipped.
+    MessageTooLarge { limit: usize },
+    /// A message's base64 payloads totaled `size` bytes, more than
+    /// [`Limits::max_payload_size`].
+    PayloadTooLarge { size: usize, limit: usize },
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::Json(err) => write!(f, "malformed message: {err}"),
+            Self::MessageTooLarge { limit } => {
+                write!(f, "message exceeded the maximum size of {limit} bytes")
+            }
+            Self::PayloadTooLarge { size, limit } => write!(
+                f,
+                "message payload of {size} bytes exceeded the maximum size of {limit} bytes"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Json(err) => Some(err),
+            Self::MessageTooLarge { .. } | Self::PayloadTooLarge { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for CodecError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for CodecError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Encodes a message as a single line of JSON, including the trailing newline.
+pub fn encode<T: Serialize>(message: &T) -> Result<String, CodecError> {
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    Ok(line)
+}
+
+/// Decodes a message from a single line of JSON.
+///
+/// Surrounding whitespace, including the trailing newline, is ignored.
+pub fn decode<T: DeserializeOwned>(line: &str) -> Result<T, CodecError> {
+    Ok(serde_json::from_str(line.trim())?)
+}
+
+/// Size limits enforced while reading messages, protecting against peers that send enormous
+/// lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Limits {
+    /// The maximum length of a single line, in bytes.
+    pub max_message_size: usize,
+    /// The maximum combined length of the base64-encoded payloads in a single event or
+    /// command, in bytes.
+    pub max_payload_size: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_message_size: 16 * 1024 * 1024,
+            max_payload_size: 8 * 1024 * 1024,
+        }
+    }
+}
+
+/// Reads newline-delimited messages from a stream.
+#[derive(Debug)]
+pub struct Reader<R> {
+    inner: R,
+    line: Vec<u8>,
+    /// Whether `line` holds a complete line that has already been returned.
+    consumed: bool,
+    /// Whether the line being read has exceeded the size limit and is being skipped.
+    skipping: bool,
+    limits: Limits,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a reader with the default [`Limits`].
+    pub fn new(inner: R) -> Self {
+        Self::with_limits(inner, Limits::default())
+    }
+
+    pub fn with_limits(inner: R, limits: Limits) -> Self {
+        Self {
+            inner,
+            line: Vec::new(),
+            consumed: false,
+            skipping: false,
+            limits,
+        }
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Reads the next message, returning `None` once the stream has ended.
+    ///
+    /// Blank lines are skipped. Lines longer than [`Limits::max_message_size`] are skipped
+    /// without being buffered, and reported with [`CodecError::MessageTooLarge`].
+    pub fn read<T: DeserializeOwned>(&mut self) -> Result<Option<T>, CodecError> {
+        loop {
+            match self.read_line(false)? {
+                Line::Complete => {}
+                Line::Pending => unreachable!("blocking reads never return a partial line"),
+                Line::Eof => return Ok(None),
+            }
+            if let Some(message) = self.parse_line()? {
+                return Ok(Some(message));
+            }
+        }
+    }
+
+    /// Reads the next message if a complete line is available, returning `None` without
+    /// blocking otherwise.
+    ///
+    /// The underlying stream must be in non-blocking mode, reporting that no data is available
+    /// with [`io::ErrorKind::WouldBlock`]. Partial lines are kept until the rest of the line
+    /// arrives. Once the stream has ended, this fails with [`io::ErrorKind::UnexpectedEof`].
+    pub fn try_read<T: DeserializeOwned>(&mut self) -> Result<Option<T>, CodecError> {
+        loop {
+            match self.read_line(true)? {
+                Line::Complete => {}
+                Line::Pending => return Ok(None),
+                Line::Eof => return Err(CodecError::Io(io::ErrorKind::UnexpectedEof.into())),
+            }
+            if let Some(message) = self.parse_line()? {
+                return Ok(Some(message));
+            }
+        }
+    }
+
+    /// Parses the line in the line buffer, returning `None` if it is blank.
+    fn parse_line<T: DeserializeOwned>(&self) -> Result<Option<T>, CodecError> {
+        let line = std::str::from_utf8(&self.line)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        if line.trim().is_empty() {
+            Ok(None)
+        } else {
+            decode(line).map(Some)
+        }
+    }
+
+    /// Reads one line into the line buffer.
+    ///
+    /// If `nonblocking` is set, a stream with no data available returns [`Line::Pending`],
+    /// keeping any partial line for the next call.
+    fn read_line(&mut self, nonblocking: bool) -> Result<Line, CodecError> {
+        if self.consumed {
+            self.line.clear();
+            self.consumed = false;
+        }
+        loop {
+            let available = match self.inner.fill_buf() {
+                Ok(available) => available,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) if nonblocking && err.kind() == io::ErrorKind::WouldBlock => {
+                    return Ok(Line::Pending);
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if available.is_empty() {
+                if self.skipping {
+                    self.skipping = false;
+                    return Err(CodecError::MessageTooLarge {
+                        limit: self.limits.max_message_size,
+                    });
+                }
+                if self.line.is_empty() {
+                    return Ok(Line::Eof);
+                }
+                self.consumed = true;
+                return Ok(Line::Complete);
+            }
+
+            let (chunk, done) = match available.iter().position(|&b| b == b'\n') {
+                Some(end) => (&available[..=end], true),
+                None => (available, false),
+            };
+            let len = chunk.len();
+            if !self.skipping && self.line.len() + len > self.limits.max_message_size {
+                self.skipping = true;
+                self.line.clear();
+            }
+            if !self.skipping {
+                self.line.extend_from_slice(chunk);
+            }
+            self.inner.consume(len);
+
+            if done {
+                self.consumed = true;
+                if self.skipping {
+                    self.skipping = false;
+                    return Err(CodecError::MessageTooLarge {
+                        limit: self.limits.max_message_size,
+                    });
+                }
+                return Ok(Line::Complete);
+            }
+        }
+    }
+
+    fn check_payload(&self, size: usize) -> Result<(), CodecError> {
+        if size > self.limits.max_payload_size {
+            return Err(CodecError::PayloadTooLarge {
+                size,
+                limit: self.limits.max_payload_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Skips lines until a [`Event::Sync`] or [`Command::Sync`] marker, returning the marker's
+    /// sequence number, or `None` if the stream ended first.
+    ///
+    /// Call this after [`Reader::read`] fails with [`CodecError::Json`] or
+    /// [`CodecError::MessageTooLarge`] to recover without closing the session. Messages between
+    /// the malformed line and the marker are discarded.
+    pub fn resync(&mut self) -> Result<Option<u64>, CodecError> {
+        #[derive(Deserialize)]
+        enum Marker {
+            Sync { seq: u64 },
+        }
+
+        loop {
+            match self.read_line(false) {
+                Ok(Line::Complete) => {}
+                Ok(Line::Pending | Line::Eof) => return Ok(None),
+                Err(CodecError::MessageTooLarge { .. }) => continue,
+                Err(err) => return Err(err),
+            }
+            let Ok(line) = std::str::from_utf8(&self.line) else {
+                continue;
+            };
+            if let Ok(Marker::Sync { seq }) = decode(line) {
+                return Ok(Some(seq));
+            }
+        }
+    }
+
+    /// Reads the next event sent by a simulator without blocking. See [`Reader::try_read`].
+    pub fn try_read_event(&mut self) -> Result<Option<Event>, CodecError> {
+        let event: Option<Event> = self.try_read()?;
+        if let Some(event) = &event {
+            self.check_payload(event.payload_len())?;
+        }
+        Ok(event)
+    }
+
+    /// Reads the next command sent by a frontend without blocking. See [`Reader::try_read`].
+    pub fn try_read_command(&mut self) -> Result<Option<Command>, CodecError> {
+        let command: Option<Command> = self.try_read()?;
+        if let Some(command) = &command {
+            self.check_payload(command.payload_len())?;
+        }
+        Ok(command)
+    }
+
+    /// Reads the next event sent by a simulator.
+    pub fn read_event(&mut self) -> Result<Option<Event>, CodecError> {
+        let event: Option<Event> = self.read()?;
+        if let Some(event) = &event {
+            self.check_payload(event.payload_len())?;
+        }
+        Ok(event)
+    }
+
+    /// Reads the next command sent by a frontend.
+    pub fn read_command(&mut self) -> Result<Option<Command>, CodecError> {
+        let command: Option<Command> = self.read()?;
+        if let Some(command) = &command {
+            self.check_payload(command.payload_len())?;
+        }
+        Ok(command)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead + ReadTimeout> Reader<R> {
+    /// Reads the next event, failing with [`io::ErrorKind::TimedOut`] if none arrives before
+    /// `deadline`.
+    ///
+    /// The stream's read timeout is restored afterwards. Part of a line received before the
+    /// deadline is kept, and finished by the next read.
+    pub fn read_event_until(&mut self, deadline: Instant) -> Result<Option<Event>, CodecError> {
+        self.read_until(deadline, Self::read_event)
+    }
+
+    /// Reads the next command, failing with [`io::ErrorKind::TimedOut`] if none arrives before
+    /// `deadline`. See [`Reader::read_event_until`].
+    pub fn read_command_until(&mut self, deadline: Instant) -> Result<Option<Command>, CodecError> {
+        self.read_until(deadline, Self::read_command)
+    }
+
+    fn read_until<T>(
+        &mut self,
+        deadline: Instant,
+        read: fn(&mut Self) -> Result<Option<T>, CodecError>,
+    ) -> Result<Option<T>, CodecError> {
+        let previous = self.inner.read_timeout()?;
+        let result = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                break Err(CodecError::Io(io::ErrorKind::TimedOut.into()));
+            }
+            if let Err(err) = self.inner.set_read_timeout(Some(remaining)) {
+                break Err(err.into());
+            }
+            match read(self) {
+                // Streams report an expired timeout as either kind, depending on the platform.
+                Err(CodecError::Io(err))
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                    ) => {}
+                result => break result,
+            }
+        };
+        self.inner.set_read_timeout(previous)?;
+        result
+    }
+}
+
+/// A stream whose blocking reads can be limited by a timeout, so that a reader can stop
+/// waiting for a peer that has gone silent.
+///
+/// In-memory streams never block, so their timeouts have no effect.
+pub trait ReadTimeout {
+    /// The current read timeout, or `None` if reads may block forever.
+    fn read_timeout(&self) -> io::Result<Option<Duration>>;
+
+    /// Sets how long a read may block before failing with [`io::ErrorKind::TimedOut`] or
+    /// [`io::ErrorKind::WouldBlock`], or removes the limit if `timeout` is `None`.
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+impl ReadTimeout for std::net::TcpStream {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        std::net::TcpStream::read_timeout(self)
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::net::TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(unix)]
+impl ReadTimeout for std::os::unix::net::UnixStream {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        std::os::unix::net::UnixStream::read_timeout(self)
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+impl<R: ReadTimeout> ReadTimeout for io::BufReader<R> {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        self.get_ref().read_timeout()
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.get_ref().set_read_timeout(timeout)
+    }
+}
+
+impl<R: ReadTimeout + ?Sized> ReadTimeout for Box<R> {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        (**self).read_timeout()
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+impl<R: ReadTimeout + ?Sized> ReadTimeout for &mut R {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        (**self).read_timeout()
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+impl ReadTimeout for &[u8] {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(None)
+    }
+
+    fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<T> ReadTimeout for io::Cursor<T> {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(None)
+    }
+
+    fn set_read_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The result of reading a line.
+enum Line {
+    /// A complete line is in the line buffer.
+    Complete,
+    /// No more data is available yet.
+    Pending,
+    /// The stream has ended.
+    Eof,
+}
+
+/// Writes newline-delimited messages to a stream.
+#[derive(Debug)]
+pub struct Writer<W> {
+    inner: W,
+    version: i32,
+}
+
+impl<W: Write> Writer<W> {
+    /// Creates a writer that encodes events for [`PROTOCOL_VERSION`].
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            version: PROTOCOL_VERSION,
+        }
+    }
+
+    /// The protocol version events are encoded for.
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Sets the protocol version events are encoded for, so that peers speaking an older
+    /// version receive events in the form they expect. The handshake functions set this to
+    /// the negotiated version.
+    pub fn set_version(&mut self, version: i32) {
+        self.version = version;
+    }
+
+    /// Writes a message followed by a newline.
+    ///
+    /// The stream is not flushed; call [`Writer::flush`] once a batch of messages has been written.
+    pub fn write<T: Serialize>(&mut self, message: &T) -> Result<(), CodecError> {
+        self.inner.write_all(encode(message)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes an event to a frontend, encoded for [`Writer::version`].
+    pub fn write_event(&mut self, event: &Event) -> Result<(), CodecError> {
+        if self.version == v1::VERSION {
+            self.write(&v1::Event::from(event.clone()))
+        } else {
+            self.write(event)
+        }
+    }
+
+    /// Writes a command to a simulator.
+    pub fn write_command(&mut self, command: &Command) -> Result<(), CodecError> {
+        self.write(command)
+    }
+
+    pub fn flush(&mut self) -> Result<(), CodecError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// A compression scheme applied to the message stream after the handshake.
+///
+/// Both handshake messages are always sent uncompressed. If both sides advertise a compression
+/// extension, every byte each side sends after its own handshake message is compressed.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum Compression {
+    #[default]
+    #[serde(rename = "None")]
+    None,
+    #[serde(rename = "Zstd")]
+    Zstd,
+}
+
+impl Compression {
+    /// The extension that enables this compression scheme.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::None => None,
+            Self::Zstd => Some(extensions::ZSTD_COMPRESSION),
+        }
+    }
+
+    /// Chooses the compression scheme to use given the extensions advertised by each side.
+    pub fn negotiate(ours: &[String], theirs: &[String]) -> Self {
+        let supported = |extension: &str| {
+            ours.iter().any(|ext| ext == extension) && theirs.iter().any(|ext| ext == extension)
+        };
+        if supported(extensions::ZSTD_COMPRESSION) {
+            Self::Zstd
+        } else {
+            Self::None
+        }
+    }
+}
+
+/// What one side of a connection offers and requires during the handshake.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct HandshakeOptions {
+    /// The protocol versions this side can speak.
+    pub versions: Vec<i32>,
+    /// The extensions this side supports.
+    pub extensions: Vec<String>,
+    /// The extensions the peer must also support for the session to continue.
+    pub required_extensions: Vec<String>,
+    /// How long to wait for the peer's handshake.
+    pub timeout: Duration,
+    /// The frame rate sent in [`Command::Handshake`]. Ignored by simulators.
+    pub frame_rate: Option<f64>,
+}
+
+impl Default for HandshakeOptions {
+    fn default() -> Self {
+        Self {
+            versions: vec![v1::VERSION, v2::VERSION],
+            extensions: Vec::new(),
+            required_extensions: Vec::new(),
+            timeout: Duration::from_secs(5),
+            frame_rate: None,
+        }
+    }
+}
+
+/// The outcome of a successful handshake.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Negotiated {
+    /// The protocol version both sides will speak.
+    pub version: i32,
+    /// The extensions advertised by both sides.
+    pub extensions: Vec<String>,
+    /// The frame rate sent by the frontend, if any.
+    pub frame_rate: Option<f64>,
+}
+
+impl Negotiated {
+    /// Whether both sides advertised `extension`.
+    pub fn has_extension(&self, extension: &str) -> bool {
+        self.extensions.iter().any(|ext| ext == extension)
+    }
+
+    /// The compression scheme to switch to after the handshake.
+    pub fn compression(&self) -> Compression {
+        Compression::negotiate(&self.extensions, &self.extensions)
+    }
+
+    /// Describes the session, taking the current time as its start time.
+    ///
+    /// The handshake does not identify either side, so the returned info has no
+    /// implementations set.
+    pub fn session_info(&self) -> SessionInfo {
+        let start_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        SessionInfo {
+            capabilities: self.extensions.clone(),
+            ..SessionInfo::new(self.version, start_time)
+        }
+    }
+}
+
+/// An error that prevented a handshake from completing.
+#[derive(Debug)]
+pub enum HandshakeError {
+    Codec(CodecError),
+    /// The peer did not send its handshake before the timeout.
+    Timeout,
+    /// The peer closed the connection before sending its handshake.
+    Disconnected,
+    /// The peer sent a message other than a handshake first.
+    UnexpectedMessage {
+        kind: &'static str,
+    },
+    /// The peer asked for a protocol version this side does not support.
+    UnsupportedVersion {
+        version: i32,
+    },
+    /// One side did not advertise extensions the other requires.
+    MissingExtensions {
+        extensions: Vec<String>,
+    },
+    /// The peer refused this side's handshake.
+    Rejected(HandshakeRejection),
+}
+
+impl HandshakeError {
+    /// The rejection to send the peer for a handshake this side refused, or `None` if the
+    /// error was not caused by the peer's handshake.
+    fn rejection(&self, options: &HandshakeOptions) -> Option<HandshakeRejection> {
+        match self {
+            Self::UnsupportedVersion { version } => Some(HandshakeRejection::UnsupportedVersion {
+                offered: *version,
+                supported: options.versions.clone(),
+            }),
+            Self::MissingExtensions { extensions } => Some(HandshakeRejection::MissingExtensions {
+                extensions: extensions.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for HandshakeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Codec(err) => write!(f, "{err}"),
+            Self::Timeout => f.write_str("timed out waiting for the peer's handshake"),
+            Self::Disconnected => f.write_str("peer disconnected before completing the handshake"),
+            Self::UnexpectedMessage { kind } => {
+                write!(f, "expected a handshake, but the peer sent {kind}")
+            }
+            Self::UnsupportedVersion { version } => {
+                write!(f, "protocol version {version} is not supported")
+            }
+            Self::MissingExtensions { extensions } => {
+                write!(f, "missing required extensions: {}", extensions.join(", "))
+            }
+            Self::Rejected(rejection) => write!(f, "the peer rejected the handshake: {rejection}"),
+        }
+    }
+}
+
+impl std::error::Error for HandshakeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Codec(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<CodecError> for HandshakeError {
+    fn from(err: CodecError) -> Self {
+        match err {
+            CodecError::Io(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+                ) =>
+            {
+                Self::Timeout
+            }
+            err => Self::Codec(err),
+        }
+    }
+}
+
+/// Performs the handshake from the frontend's side: sends [`Command::Handshake`] offering the
+/// newest supported version, then waits for the simulator's [`Event::Handshake`].
+///
+/// If the simulator's reply is unacceptable, [`Command::HandshakeRejected`] is sent explaining
+/// why. A rejection from the simulator is returned as [`HandshakeError::Rejected`].
+///
+/// Fails with [`HandshakeError::Timeout`] if the simulator's reply does not arrive within
+/// [`HandshakeOptions::timeout`] of this call, even if the simulator sends nothing at all.
+pub fn negotiate_as_frontend<R: BufRead + ReadTimeout, W: Write>(
+    reader: &mut Reader<R>,
+    writer: &mut Writer<W>,
+    options: &HandshakeOptions,
+) -> Result<Negotiated, HandshakeError> {
+    let deadline = Instant::now() + options.timeout;
+    let offered = options
+        .versions
+        .iter()
+        .copied()
+        .max()
+        .unwrap_or(PROTOCOL_VERSION);
+    writer.write_command(&Command::Handshake {
+        version: offered,
+        extensions: options.extensions.clone(),
+        frame_rate: options.frame_rate,
+    })?;
+    writer.flush()?;
+
+    let event = reader
+        .read_event_until(deadline)?
+        .ok_or(HandshakeError::Disconnected)?;
+    let (version, extensions) = match event {
+        Event::Handshake {
+            version,
+            extensions,
+        } => (version, extensions),
+        Event::HandshakeRejected(rejection) => return Err(HandshakeError::Rejected(rejection)),
+        event => return Err(HandshakeError::UnexpectedMessage { kind: event.kind() }),
+    };
+    let result = if options.versions.contains(&version) {
+        finish(version, &extensions, options, options.frame_rate)
+    } else {
+        Err(HandshakeError::UnsupportedVersion { version })
+    };
+    match &result {
+        Ok(negotiated) => writer.set_version(negotiated.version),
+        Err(err) => {
+            if let Some(rejection) = err.rejection(options) {
+                writer.write_command(&Command::HandshakeRejected(rejection))?;
+                writer.flush()?;
+            }
+        }
+    }
+    result
+}
+
+/// Performs the handshake from the simulator's side: waits for the frontend's
+/// [`Command::Handshake`], then replies with [`Event::Handshake`].
+///
+/// If the frontend offers a newer version than this side supports, the reply downgrades to the
+/// newest version supported by this side that is older than the frontend's, which the frontend
+/// may accept or reject. If the frontend's version or extensions are unacceptable, the reply
+/// is [`Event::HandshakeRejected`] explaining why, so that the frontend can report the mismatch
+/// too. Fails with [`HandshakeError::Timeout`] if the frontend's handshake does not arrive
+/// within [`HandshakeOptions::timeout`] of this call.
+pub fn negotiate_as_simulator<R: BufRead + ReadTimeout, W: Write>(
+    reader: &mut Reader<R>,
+    writer: &mut Writer<W>,
+    options: &HandshakeOptions,
+) -> Result<Negotiated, HandshakeError> {
+    let deadline = Instant::now() + options.timeout;
+    let command = reader
+        .read_command_until(deadline)?
+        .ok_or(HandshakeError::Disconnected)?;
+    let Command::Handshake {
+        version: offered,
+        extensions,
+        frame_rate,
+    } = command
+    else {
+        return Err(HandshakeError::UnexpectedMessage {
+            kind: command.kind(),
+        });
+    };
+
+    let result = options
+        .versions
+        .iter()
+        .copied()
+        .filter(|&version| version <= offered)
+        .max()
+        .ok_or(HandshakeError::UnsupportedVersion { version: offered })
+        .and_then(|version| finish(version, &extensions, options, frame_rate));
+    match &result {
+        Ok(negotiated) => {
+            writer.set_version(negotiated.version);
+            writer.write_event(&Event::Handshake {
+                version: negotiated.version,
+                extensions: options.extensions.clone(),
+            })?;
+        }
+        Err(err) => {
+            if let Some(rejection) = err.rejection(options) {
+                writer.write_event(&Event::HandshakeRejected(rejection))?;
+            }
+        }
+    }
+    writer.flush()?;
+    result
+}
+
+/// Checks the peer's extensions against this side's requirements.
+fn finish(
+    version: i32,
+    theirs: &[String],
+    options: &HandshakeOptions,
+    frame_rate: Option<f64>,
+) -> Result<Negotiated, HandshakeError> {
+    let missing: Vec<String> = options
+        .required_extensions
+        .iter()
+        .filter(|ext| !theirs.contains(ext))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(HandshakeError::MissingExtensions {
+            extensions: missing,
+        });
+    }
+    Ok(Negotiated {
+        version,
+        extensions: options
+            .extensions
+            .iter()
+            .filter(|ext| theirs.contains(ext))
+            .cloned()
+            .collect(),
+        frame_rate,
+    })
+}
+
+#[cfg(feature = "zstd")]
+impl<R: BufRead> Reader<R> {
+    /// Switches to reading a zstd-compressed stream.
+    ///
+    /// Call this after reading the peer's handshake if [`Compression::Zstd`] was negotiated.
+    pub fn into_zstd(self) -> Result<Reader<io::BufReader<zstd::Decoder<'static, R>>>, CodecError> {
+        let decoder = zstd::Decoder::with_buffer(self.inner)?;
+        Ok(Reader::with_limits(
+            io::BufReader::new(decoder),
+            self.limits,
+        ))
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<W: Write> Writer<W> {
+    /// Switches to writing a zstd-compressed stream.
+    ///
+    /// Call this after writing the handshake if [`Compression::Zstd`] was negotiated. Each call
+    /// to [`Writer::flush`] ends a compressed block so the peer can decode everything written
+    /// so far.
+    pub fn into_zstd(self) -> Result<Writer<zstd::Encoder<'static, W>>, CodecError> {
+        let encoder = zstd::Encoder::new(self.inner, 0)?;
+        Ok(Writer {
+            inner: encoder,
+            version: self.version,
+        })
+    }
+}
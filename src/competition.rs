@@ -0,0 +1,135 @@
+//! Competition control state.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CompetitionMode {
+    pub enabled: bool,
+    pub connected: bool,
+    pub mode: CompMode,
+    pub is_competition: bool,
+}
+
+impl Default for CompetitionMode {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            connected: false,
+            mode: CompMode::Driver,
+            is_competition: false,
+        }
+    }
+}
+
+/// The current stage of a competition.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
+)]
+pub enum CompMode {
+    #[serde(rename = "Auto")]
+    Auto,
+    #[default]
+    #[serde(rename = "Driver")]
+    Driver,
+}
+
+/// One step of a [`Command::CompetitionSchedule`].
+///
+/// [`Command::CompetitionSchedule`]: crate::Command::CompetitionSchedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CompetitionPhase {
+    pub mode: CompetitionMode,
+    /// How long the mode lasts, in milliseconds.
+    pub duration_ms: u64,
+}
+
+impl CompetitionPhase {
+    /// The phases of a match as run by field control: autonomous, a disabled gap, driver
+    /// control, and then disabled once the match ends.
+    ///
+    /// The robot is disabled between periods just as it is on a real field, so code that
+    /// assumes it moves straight from autonomous to driver control can be tested.
+    pub fn match_schedule(auton_ms: u64, gap_ms: u64, driver_ms: u64) -> Vec<Self> {
+        let mode = |enabled, mode| CompetitionMode {
+            enabled,
+            connected: true,
+            mode,
+            is_competition: true,
+        };
+        vec![
+            Self {
+                mode: mode(true, CompMode::Auto),
+                duration_ms: auton_ms,
+            },
+            Self {
+                mode: mode(false, CompMode::Auto),
+                duration_ms: gap_ms,
+            },
+            Self {
+                mode: mode(true, CompMode::Driver),
+                duration_ms: driver_ms,
+            },
+            Self {
+                mode: mode(false, CompMode::Driver),
+                duration_ms: 0,
+            },
+        ]
+    }
+
+    /// The mode in effect `elapsed_ms` milliseconds after a schedule was started, or `None` if
+    /// the schedule is empty. The last phase's mode stays in effect once the schedule ends.
+    pub fn mode_at(schedule: &[Self], elapsed_ms: u64) -> Option<CompetitionMode> {
+        let mut end = 0u64;
+        for phase in schedule {
+            end = end.saturating_add(phase.duration_ms);
+            if elapsed_ms < end {
+                return Some(phase.mode);
+            }
+        }
+        schedule.last().map(|phase| phase.mode)
+    }
+
+    /// The time each phase of a schedule takes effect, in milliseconds after the schedule was
+    /// started. Phases that last no time are skipped, except for the last one.
+    pub fn starts(schedule: &[Self]) -> Vec<(u64, CompetitionMode)> {
+        let mut start = 0u64;
+        let mut starts = Vec::new();
+        for (index, phase) in schedule.iter().enumerate() {
+            if phase.duration_ms > 0 || index == schedule.len() - 1 {
+                starts.push((start, phase.mode));
+            }
+            start = start.saturating_add(phase.duration_ms);
+        }
+        starts
+    }
+}
+
+/// Rule-related totals for a robot's configuration.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct LegalityReport {
+    /// The number of smart motors configured.
+    pub motor_count: u32,
+    /// The combined rated power of all configured motors, in watts.
+    pub total_motor_power: f64,
+    /// The number of devices configured on all ports.
+    pub device_count: u32,
+    pub warnings: Vec<LegalityWarning>,
+}
+
+/// A way in which a robot's configuration may not be competition legal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct LegalityWarning {
+    /// The game manual rule that may be violated, such as `"R15"`.
+    pub rule: Option<String>,
+    pub message: String,
+}
+
+/// An autonomous routine that can be chosen before a match.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AutonRoutine {
+    /// A stable identifier for the routine, used to select it.
+    pub id: String,
+    /// A human-readable name for the routine.
+    pub name: String,
+    pub description: Option<String>,
+}
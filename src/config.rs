@@ -0,0 +1,80 @@
+//! A standard file format describing a simulated robot.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controller::ControllerUpdate,
+    devices::{DeviceConfig, GpsField, SmartPort},
+    Command,
+};
+
+/// A description of a robot and its surroundings.
+///
+/// Robot configuration files are JSON-encoded `RobotConfig`s. A frontend applies one by sending
+/// the commands returned by [`RobotConfig::to_commands`] before [`Command::StartExecution`].
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct RobotConfig {
+    /// The devices connected to the robot.
+    #[serde(default)]
+    pub devices: Vec<DeviceConfig>,
+    /// The layout of the robot's drivetrain, used by frontends to simulate its motion.
+    #[serde(default)]
+    pub drivetrain: Option<Drivetrain>,
+    /// The physical controllers to use for each robot controller.
+    #[serde(default)]
+    pub controllers: ControllerBindings,
+    /// The placement of the field's GPS code strips.
+    #[serde(default)]
+    pub field: Option<GpsField>,
+}
+
+impl RobotConfig {
+    /// The commands that set up a simulator to match this configuration.
+    pub fn to_commands(&self) -> Vec<Command> {
+        let mut commands = vec![Command::ConfigureDevices(self.devices.clone())];
+        if let Some(field) = self.field {
+            commands.push(Command::SetGpsField(field));
+        }
+        if self.controllers.primary.is_some() || self.controllers.partner.is_some() {
+            commands.push(Command::ControllerUpdate(
+                self.controllers.primary.clone().map(ControllerUpdate::UUID),
+                self.controllers.partner.clone().map(ControllerUpdate::UUID),
+            ));
+        }
+        commands
+    }
+
+    /// Parses a robot configuration file.
+    #[cfg(feature = "codec")]
+    pub fn from_json(json: &str) -> Result<Self, crate::codec::CodecError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Encodes this configuration as a human-readable robot configuration file.
+    #[cfg(feature = "codec")]
+    pub fn to_json(&self) -> Result<String, crate::codec::CodecError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// A differential ("tank") drivetrain.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Drivetrain {
+    pub left_motors: Vec<SmartPort>,
+    pub right_motors: Vec<SmartPort>,
+    /// The diameter of the drive wheels, in meters.
+    pub wheel_diameter: f64,
+    /// The distance between the left and right wheels, in meters.
+    pub track_width: f64,
+    /// The ratio of wheel speed to motor output shaft speed.
+    pub gear_ratio: f64,
+}
+
+/// The UUIDs of the physical controllers used for each robot controller.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ControllerBindings {
+    #[serde(default)]
+    pub primary: Option<String>,
+    #[serde(default)]
+    pub partner: Option<String>,
+}
@@ -0,0 +1,122 @@
+//! VEX V5 controller input.
+
+use serde::{Deserialize, Serialize};
+
+/// A method of retrieving a controller's current state.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ControllerUpdate {
+    /// Implementors can send raw controller state to the simulator,
+    /// allowing for keyboard-and-mouse-based control.
+    #[serde(rename = "Raw")]
+    Raw(ControllerState),
+    /// Implementors can can send the UUID of a physical controller (more efficient and allows for SDL2 mappings).
+    #[serde(rename = "UUID")]
+    UUID(String),
+}
+
+/// One of the two controllers that can be connected to a robot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ControllerId {
+    #[serde(rename = "Primary")]
+    Primary,
+    #[serde(rename = "Partner")]
+    Partner,
+}
+
+/// A physical game controller connected to the frontend.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PhysicalController {
+    /// The identifier used to refer to this controller in [`ControllerUpdate::UUID`].
+    pub uuid: String,
+    /// A human-readable name for the controller.
+    pub name: String,
+    pub kind: PhysicalControllerKind,
+}
+
+/// The type of a physical game controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum PhysicalControllerKind {
+    /// A VEX V5 controller tethered to the computer.
+    #[serde(rename = "V5Controller")]
+    V5Controller,
+    /// A standard gamepad, such as an Xbox or PlayStation controller.
+    #[serde(rename = "Gamepad")]
+    Gamepad,
+    /// A flight stick or other joystick.
+    #[serde(rename = "Joystick")]
+    Joystick,
+    #[serde(rename = "Other")]
+    Other,
+}
+
+/// Processing a frontend applies to joystick axes before sending them in [`ControllerState`].
+///
+/// The default is no processing, meaning axis values are sent exactly as read from the
+/// controller.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct InputProcessing {
+    /// Axis values with a magnitude at or below this are reported as zero.
+    pub deadband: u8,
+    pub curve: AxisCurve,
+}
+
+impl InputProcessing {
+    /// Whether axis values are sent unmodified.
+    pub fn is_raw(&self) -> bool {
+        self.deadband == 0 && self.curve == AxisCurve::Linear
+    }
+
+    /// Applies the deadband and then the curve to a raw axis value from -127 to 127.
+    pub fn apply(&self, value: i32) -> i32 {
+        if value.unsigned_abs() <= u32::from(self.deadband) {
+            return 0;
+        }
+        match self.curve {
+            AxisCurve::Linear => value,
+            AxisCurve::Power { exponent } => {
+                let magnitude = (f64::from(value.abs()) / 127.0).powf(exponent);
+                (magnitude * 127.0).round() as i32 * value.signum()
+            }
+        }
+    }
+}
+
+/// The response curve applied to a joystick axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum AxisCurve {
+    #[default]
+    #[serde(rename = "Linear")]
+    Linear,
+    /// The axis's magnitude, as a fraction of full scale, raised to `exponent`, keeping its sign.
+    #[serde(rename = "Power")]
+    Power { exponent: f64 },
+}
+
+/// The raw state of a VEX V5 controller.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
+)]
+pub struct ControllerState {
+    pub axis1: i32,
+    pub axis2: i32,
+    pub axis3: i32,
+    pub axis4: i32,
+    pub button_l1: bool,
+    pub button_l2: bool,
+    pub button_r1: bool,
+    pub button_r2: bool,
+    pub button_up: bool,
+    pub button_down: bool,
+    pub button_left: bool,
+    pub button_right: bool,
+    pub button_x: bool,
+    pub button_b: bool,
+    pub button_y: bool,
+    pub button_a: bool,
+    pub button_sel: bool,
+    pub battery_level: i32,
+    pub button_all: bool,
+    pub flags: i32,
+    pub battery_capacity: i32,
+}
@@ -0,0 +1,150 @@
+//! Producing and applying [`StatusDelta`]s, which carry only the changed fields of a device's
+//! status.
+//!
+//! A simulator keeps a [`DeltaEncoder`] to decide whether each status can be sent as an
+//! [`Event::DeviceDelta`], and a frontend keeps the latest full status of each device (such as
+//! in a [`RobotStateTracker`](crate::state::RobotStateTracker)) and applies deltas to it with
+//! [`apply`].
+
+use std::{collections::BTreeMap, error::Error, fmt};
+
+use serde::Deserialize;
+#[cfg(feature = "codec")]
+use serde_json::{Number, Value};
+
+use crate::{
+    devices::{DeviceStatus, FieldValue, Port, StatusDelta},
+    Event,
+};
+
+mod value;
+
+#[cfg(feature = "codec")]
+pub(crate) use value::to_field_value;
+
+/// The fields that differ between two statuses, or `None` if they are different kinds of
+/// status and the new one must be sent in full.
+pub fn diff(old: &DeviceStatus, new: &DeviceStatus) -> Option<StatusDelta> {
+    let (Ok((old_kind, old)), Ok((new_kind, new))) = (fields(old), fields(new)) else {
+        return None;
+    };
+    if old_kind != new_kind {
+        return None;
+    }
+    Some(StatusDelta(
+        new.into_iter()
+            .filter(|(name, value)| old.get(name) != Some(value))
+            .collect(),
+    ))
+}
+
+/// Applies a delta to a status, returning the updated status.
+///
+/// Fields this version of the protocol doesn't know about are ignored, just as they are in a
+/// full [`Event::DeviceUpdate`]. Fails if a field has the wrong type for the status.
+pub fn apply(status: &DeviceStatus, delta: &StatusDelta) -> Result<DeviceStatus, DeltaError> {
+    let (kind, mut fields) = fields(status)?;
+    for (name, value) in &delta.0 {
+        fields.insert(name.clone(), value.clone());
+    }
+    let status = FieldValue::Object(BTreeMap::from([(kind, FieldValue::Object(fields))]));
+    DeviceStatus::deserialize(status)
+}
+
+/// The variant name and fields of a status.
+fn fields(status: &DeviceStatus) -> Result<(String, BTreeMap<String, FieldValue>), DeltaError> {
+    if let FieldValue::Object(map) = value::to_field_value(status)? {
+        if let Some((kind, FieldValue::Object(fields))) = map.into_iter().next() {
+            return Ok((kind, fields));
+        }
+    }
+    Err(DeltaError(
+        "device status is not a variant with named fields".to_string(),
+    ))
+}
+
+/// An error that occurred while applying a [`StatusDelta`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeltaError(String);
+
+impl fmt::Display for DeltaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid status delta: {}", self.0)
+    }
+}
+
+impl Error for DeltaError {}
+
+/// Tracks the statuses a simulator has sent, converting each new status into the smallest
+/// event that conveys it.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct DeltaEncoder {
+    sent: BTreeMap<Port, DeviceStatus>,
+}
+
+impl DeltaEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The event to send for a device's new status: a [`Event::DeviceDelta`] if a status of
+    /// the same kind was previously sent for the port, or a full [`Event::DeviceUpdate`]
+    /// otherwise. Returns `None` if nothing changed.
+    pub fn encode(&mut self, port: Port, status: DeviceStatus) -> Option<Event> {
+        let delta = self.sent.get(&port).and_then(|old| diff(old, &status));
+        self.sent.insert(port, status.clone());
+        match delta {
+            Some(delta) if delta.0.is_empty() => None,
+            Some(delta) => Some(Event::DeviceDelta { port, delta }),
+            None => Some(Event::DeviceUpdate { status, port }),
+        }
+    }
+
+    /// Forgets every status sent so far, so that the next status for each port is sent in
+    /// full.
+    pub fn reset(&mut self) {
+        self.sent.clear();
+    }
+}
+
+#[cfg(feature = "codec")]
+impl From<Value> for FieldValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(value),
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => Self::Integer(value),
+                None => Self::Float(number.as_f64().unwrap_or_default()),
+            },
+            Value::String(value) => Self::String(value),
+            Value::Array(values) => Self::Array(values.into_iter().map(Self::from).collect()),
+            Value::Object(fields) => Self::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, Self::from(value)))
+                    .collect::<BTreeMap<_, _>>(),
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "codec")]
+impl From<FieldValue> for Value {
+    fn from(value: FieldValue) -> Self {
+        match value {
+            FieldValue::Null => Self::Null,
+            FieldValue::Bool(value) => Self::Bool(value),
+            FieldValue::Integer(value) => Self::Number(value.into()),
+            FieldValue::Float(value) => Number::from_f64(value).map_or(Self::Null, Self::Number),
+            FieldValue::String(value) => Self::String(value),
+            FieldValue::Array(values) => Self::Array(values.into_iter().map(Self::from).collect()),
+            FieldValue::Object(fields) => Self::Object(
+                fields
+                    .into_iter()
+                    .map(|(name, value)| (name, Self::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
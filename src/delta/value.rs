@@ -0,0 +1,657 @@
+//! Converting between serializable values and [`FieldValue`]s, using the same data model as
+//! JSON so that deltas are independent of the codec.
+
+use std::{collections::BTreeMap, fmt};
+
+use serde::{
+    de::{
+        self, value::MapDeserializer, value::SeqDeserializer, value::StringDeserializer,
+        DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess, Visitor,
+    },
+    forward_to_deserialize_any,
+    ser::{self, Impossible},
+    Deserializer, Serialize, Serializer,
+};
+
+use super::DeltaError;
+use crate::devices::FieldValue;
+
+impl ser::Error for DeltaError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl de::Error for DeltaError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Converts a value to the form it would have as JSON, keeping floats that JSON can't
+/// represent.
+pub(crate) fn to_field_value<T: Serialize + ?Sized>(value: &T) -> Result<FieldValue, DeltaError> {
+    value.serialize(ValueSerializer)
+}
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, value: bool) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Integer(value))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<FieldValue, DeltaError> {
+        self.serialize_i64(value.into())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<FieldValue, DeltaError> {
+        Ok(match i64::try_from(value) {
+            Ok(value) => FieldValue::Integer(value),
+            Err(_) => FieldValue::Float(value as f64),
+        })
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<FieldValue, DeltaError> {
+        self.serialize_f64(value.into())
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Float(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::String(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::String(value.to_string()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Array(
+            value
+                .iter()
+                .map(|&byte| FieldValue::Integer(byte.into()))
+                .collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<FieldValue, DeltaError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<FieldValue, DeltaError> {
+        Ok(FieldValue::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<FieldValue, DeltaError> {
+        self.serialize_str(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, DeltaError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, DeltaError> {
+        Ok(tagged(variant, to_field_value(value)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, DeltaError> {
+        Ok(SeqSerializer {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, DeltaError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, DeltaError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, DeltaError> {
+        Ok(SeqSerializer {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, DeltaError> {
+        Ok(MapSerializer {
+            variant: None,
+            fields: BTreeMap::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, DeltaError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<MapSerializer, DeltaError> {
+        Ok(MapSerializer {
+            variant: Some(variant),
+            fields: BTreeMap::new(),
+            key: None,
+        })
+    }
+}
+
+/// An externally tagged enum variant.
+fn tagged(variant: &str, value: FieldValue) -> FieldValue {
+    FieldValue::Object(BTreeMap::from([(variant.to_string(), value)]))
+}
+
+struct SeqSerializer {
+    variant: Option<&'static str>,
+    items: Vec<FieldValue>,
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        self.items.push(to_field_value(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<FieldValue, DeltaError> {
+        let items = FieldValue::Array(self.items);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, items),
+            None => items,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+struct MapSerializer {
+    variant: Option<&'static str>,
+    fields: BTreeMap<String, FieldValue>,
+    key: Option<String>,
+}
+
+impl MapSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), DeltaError> {
+        self.fields.insert(key.to_string(), to_field_value(value)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<FieldValue, DeltaError> {
+        let fields = FieldValue::Object(self.fields);
+        Ok(match self.variant {
+            Some(variant) => tagged(variant, fields),
+            None => fields,
+        })
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), DeltaError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), DeltaError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| DeltaError("map value without a key".to_string()))?;
+        self.insert(&key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), DeltaError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = FieldValue;
+    type Error = DeltaError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), DeltaError> {
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, DeltaError> {
+        self.finish()
+    }
+}
+
+/// Serializes map keys, which like JSON object keys must be strings or numbers.
+struct KeySerializer;
+
+impl KeySerializer {
+    fn unsupported<T>(self) -> Result<T, DeltaError> {
+        Err(DeltaError(
+            "map keys must be strings or integers".to_string(),
+        ))
+    }
+}
+
+impl Serializer for KeySerializer {
+    type Ok = String;
+    type Error = DeltaError;
+    type SerializeSeq = Impossible<String, DeltaError>;
+    type SerializeTuple = Impossible<String, DeltaError>;
+    type SerializeTupleStruct = Impossible<String, DeltaError>;
+    type SerializeTupleVariant = Impossible<String, DeltaError>;
+    type SerializeMap = Impossible<String, DeltaError>;
+    type SerializeStruct = Impossible<String, DeltaError>;
+    type SerializeStructVariant = Impossible<String, DeltaError>;
+
+    fn serialize_bool(self, _value: bool) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_f32(self, _value: f32) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_f64(self, _value: f64) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_char(self, value: char) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<String, DeltaError> {
+        Ok(value.to_string())
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_none(self) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_unit(self) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, DeltaError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, DeltaError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, DeltaError> {
+        self.unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, DeltaError> {
+        self.unsupported()
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DeltaError> for FieldValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for FieldValue {
+    type Error = DeltaError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeltaError> {
+        match self {
+            Self::Null => visitor.visit_unit(),
+            Self::Bool(value) => visitor.visit_bool(value),
+            Self::Integer(value) => visitor.visit_i64(value),
+            Self::Float(value) => visitor.visit_f64(value),
+            Self::String(value) => visitor.visit_string(value),
+            Self::Array(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            Self::Object(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeltaError> {
+        match self {
+            Self::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeltaError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeltaError> {
+        match self {
+            Self::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Self::Object(fields) if fields.len() == 1 => {
+                let (variant, value) = fields.into_iter().next().unwrap();
+                visitor.visit_enum(Variant { variant, value })
+            }
+            _ => Err(DeltaError(
+                "expected a string or an object with one field".to_string(),
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// An externally tagged enum variant with a payload.
+struct Variant {
+    variant: String,
+    value: FieldValue,
+}
+
+impl<'de> EnumAccess<'de> for Variant {
+    type Error = DeltaError;
+    type Variant = FieldValue;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<(S::Value, FieldValue), DeltaError> {
+        let variant: StringDeserializer<DeltaError> = self.variant.into_deserializer();
+        Ok((seed.deserialize(variant)?, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for FieldValue {
+    type Error = DeltaError;
+
+    fn unit_variant(self) -> Result<(), DeltaError> {
+        match self {
+            Self::Null => Ok(()),
+            _ => Err(DeltaError("expected a unit variant".to_string())),
+        }
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(
+        self,
+        seed: S,
+    ) -> Result<S::Value, DeltaError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeltaError> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeltaError> {
+        self.deserialize_any(visitor)
+    }
+}
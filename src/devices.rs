@@ -0,0 +1,934 @@
+//! Configuration and status of V5 peripherals and the ports they are connected to.
+
+use mint::{Point2, Quaternion, Vector3};
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    controller::ControllerId,
+    screen::Color,
+    vision::{AprilTagDetection, AprilTagFamily, ColorBlob, ModelDetection, VisionObject},
+};
+
+/// The configuration of a V5 peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Device {
+    #[serde(rename = "Motor")]
+    Motor {
+        physical_gearset: MotorGearset,
+        moment_of_inertia: f64,
+        /// Whether the mechanism moves backward when the motor's output shaft spins clockwise,
+        /// such as because the motor is mounted facing the other way.
+        #[serde(default)]
+        reversed: bool,
+        /// The number of turns of the motor's output shaft per turn of the mechanism it drives,
+        /// such as 60/36 for a 36-tooth gear on the motor driving a 60-tooth gear.
+        #[serde(default = "unit_ratio")]
+        external_ratio: f64,
+    },
+    #[serde(rename = "Gps")]
+    Gps {
+        mount: GpsMount,
+        /// The pose the sensor reports before it has seen the field's code strips.
+        #[serde(default)]
+        initial_pose: Option<GpsPose>,
+    },
+    #[serde(rename = "Imu")]
+    Imu {
+        orientation: ImuOrientation,
+        /// The sensor's offset from the robot's center of rotation in meters, with +x facing
+        /// the front of the robot and +y facing its left side. Used only for visualization.
+        #[serde(default)]
+        offset: Option<Point2<f64>>,
+        /// Errors to add to the sensor's readings. Defaults to none.
+        #[serde(default)]
+        drift: ImuDrift,
+    },
+    #[serde(rename = "Distance")]
+    Distance {
+        /// The width of the sensor's detection cone, in degrees.
+        field_of_view: f64,
+        /// The closest distance the sensor can measure, in millimeters.
+        min_range: f64,
+        /// The farthest distance the sensor can measure, in millimeters.
+        max_range: f64,
+        /// The fraction of emitted light reflected back by targets, from 0.0 to 1.0.
+        reflectivity: f64,
+    },
+    #[serde(rename = "RotationSensor")]
+    RotationSensor {
+        /// Whether the sensor initially counts clockwise rotation as negative.
+        #[serde(default)]
+        reversed: bool,
+    },
+    #[serde(rename = "Optical")]
+    Optical {
+        /// How long the sensor collects light for each reading, in milliseconds.
+        integration_time_ms: f64,
+    },
+    #[serde(rename = "Vision")]
+    Vision {
+        /// The horizontal field of view of the camera, in degrees.
+        horizontal_fov: f64,
+        /// The vertical field of view of the camera, in degrees.
+        vertical_fov: f64,
+    },
+    /// An AI Vision sensor.
+    #[serde(rename = "AiVision")]
+    AiVision {
+        /// The AprilTag family to detect, or `None` to disable tag detection.
+        tag_family: Option<AprilTagFamily>,
+        /// Whether to detect blobs matching the configured color signatures.
+        color_detection: bool,
+        /// Whether to run the sensor's object detection model.
+        model_detection: bool,
+    },
+    /// A V5 Workcell electromagnet.
+    #[serde(rename = "Electromagnet")]
+    Electromagnet {
+        /// The farthest an object can be from the magnet and still be picked up, in millimeters.
+        pickup_range: f64,
+    },
+    /// A 3-wire expander, which provides eight additional ADI ports addressed with
+    /// [`Port::ExpanderAdi`].
+    #[serde(rename = "AdiExpander")]
+    AdiExpander,
+    /// A digital sensor on an ADI port, such as a limit switch or bumper.
+    ///
+    /// The frontend drives the sensor with [`Command::AdiInput`]. Like the brain's pull-up
+    /// inputs, voltages above 2.5 V read as high, which is the state of a released switch.
+    ///
+    /// [`Command::AdiInput`]: crate::Command::AdiInput
+    #[serde(rename = "AdiDigitalIn")]
+    AdiDigitalIn,
+    /// A digital output on an ADI port, such as a solenoid. Its state is reported with
+    /// [`Event::AdiOutput`].
+    ///
+    /// [`Event::AdiOutput`]: crate::Event::AdiOutput
+    #[serde(rename = "AdiDigitalOut")]
+    AdiDigitalOut,
+    /// A quadrature encoder plugged into two ADI ports.
+    ///
+    /// The device is configured on the port its top wire is plugged into, which on the V5 must
+    /// be the first port of an adjacent pair.
+    #[serde(rename = "AdiEncoder")]
+    AdiEncoder {
+        /// The port the encoder's bottom wire is plugged into.
+        bottom: AdiPort,
+    },
+    /// An ultrasonic rangefinder plugged into two ADI ports.
+    ///
+    /// The device is configured on the port its output (ping) wire is plugged into.
+    #[serde(rename = "AdiUltrasonic")]
+    AdiUltrasonic {
+        /// The port the sensor's input (echo) wire is plugged into.
+        echo: AdiPort,
+    },
+    /// A single-axis yaw rate gyro plugged into an ADI port.
+    #[serde(rename = "AdiGyro")]
+    AdiGyro {
+        /// The factor the gyro's heading is multiplied by, to correct for sensor-to-sensor
+        /// variation.
+        multiplier: f64,
+    },
+    /// A potentiometer plugged into an ADI port.
+    #[serde(rename = "AdiPotentiometer")]
+    AdiPotentiometer { kind: PotentiometerKind },
+    /// A line tracker plugged into an ADI port.
+    #[serde(rename = "AdiLineTracker")]
+    AdiLineTracker,
+    /// A strip of addressable (WS2812) LEDs driven from an ADI port.
+    #[serde(rename = "AdiAddrLed")]
+    AdiAddrLed {
+        /// The number of LEDs on the strip.
+        length: u16,
+    },
+    /// A legacy 3-wire servo plugged into an ADI port. Its commanded position is reported with
+    /// [`Event::AdiPwmOutput`].
+    ///
+    /// [`Event::AdiPwmOutput`]: crate::Event::AdiPwmOutput
+    #[serde(rename = "AdiServo")]
+    AdiServo,
+    /// A legacy 2-wire motor driven through a motor controller on an ADI port. Its commanded
+    /// duty cycle is reported with [`Event::AdiPwmOutput`].
+    ///
+    /// [`Event::AdiPwmOutput`]: crate::Event::AdiPwmOutput
+    #[serde(rename = "AdiPwmMotor")]
+    AdiPwmMotor,
+    /// An analog output on an ADI port. Its voltage is reported with [`Event::AdiAnalogOutput`].
+    ///
+    /// [`Event::AdiAnalogOutput`]: crate::Event::AdiAnalogOutput
+    #[serde(rename = "AdiAnalogOut")]
+    AdiAnalogOut,
+    /// A 3-axis accelerometer plugged into three ADI ports, one per axis.
+    ///
+    /// The device is configured on the port its X axis wire is plugged into.
+    #[serde(rename = "AdiAccelerometer")]
+    AdiAccelerometer {
+        /// The port the Y axis wire is plugged into.
+        y: AdiPort,
+        /// The port the Z axis wire is plugged into.
+        z: AdiPort,
+        range: AccelerometerRange,
+    },
+    /// A pneumatic solenoid valve on an ADI port, driven like an [`AdiDigitalOut`] where a
+    /// high output extends the cylinders it controls.
+    ///
+    /// [`AdiDigitalOut`]: Device::AdiDigitalOut
+    #[serde(rename = "AdiSolenoid")]
+    AdiSolenoid,
+    /// A custom device on a smart port running in generic serial (RS-485) mode. Data is
+    /// exchanged with [`Event::PortSerial`] and [`Command::PortSerial`].
+    ///
+    /// [`Event::PortSerial`]: crate::Event::PortSerial
+    /// [`Command::PortSerial`]: crate::Command::PortSerial
+    #[serde(rename = "GenericSerial")]
+    GenericSerial {
+        /// The baud rate user code has opened the port with.
+        baud: u32,
+    },
+    /// A V5 radio, which connects the brain to controllers and field control.
+    #[serde(rename = "Radio")]
+    Radio,
+}
+
+impl Device {
+    /// The name of this device's variant, as it appears in the serialized form and in
+    /// [`Event::PortClaimed`](crate::Event::PortClaimed).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Motor { .. } => "Motor",
+            Self::Gps { .. } => "Gps",
+            Self::Imu { .. } => "Imu",
+            Self::Distance { .. } => "Distance",
+            Self::RotationSensor { .. } => "RotationSensor",
+            Self::Optical { .. } => "Optical",
+            Self::Vision { .. } => "Vision",
+            Self::AiVision { .. } => "AiVision",
+            Self::Electromagnet { .. } => "Electromagnet",
+            Self::AdiExpander => "AdiExpander",
+            Self::AdiDigitalIn => "AdiDigitalIn",
+            Self::AdiDigitalOut => "AdiDigitalOut",
+            Self::AdiEncoder { .. } => "AdiEncoder",
+            Self::AdiUltrasonic { .. } => "AdiUltrasonic",
+            Self::AdiGyro { .. } => "AdiGyro",
+            Self::AdiPotentiometer { .. } => "AdiPotentiometer",
+            Self::AdiLineTracker => "AdiLineTracker",
+            Self::AdiAddrLed { .. } => "AdiAddrLed",
+            Self::AdiServo => "AdiServo",
+            Self::AdiPwmMotor => "AdiPwmMotor",
+            Self::AdiAnalogOut => "AdiAnalogOut",
+            Self::AdiAccelerometer { .. } => "AdiAccelerometer",
+            Self::AdiSolenoid => "AdiSolenoid",
+            Self::GenericSerial { .. } => "GenericSerial",
+            Self::Radio => "Radio",
+        }
+    }
+}
+
+fn unit_ratio() -> f64 {
+    1.0
+}
+
+/// A device and the port it is connected to.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct DeviceConfig {
+    pub port: Port,
+    pub device: Device,
+}
+
+/// The outcome of applying a batch of device configurations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ConfigureDevicesResult {
+    /// Every device was configured.
+    #[serde(rename = "Applied")]
+    Applied,
+    /// No devices were configured because at least one configuration was invalid.
+    #[serde(rename = "Rejected")]
+    Rejected { errors: Vec<DeviceConfigError> },
+}
+
+/// A reason a device configuration could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct DeviceConfigError {
+    pub port: Port,
+    pub message: String,
+}
+
+/// Where a GPS sensor is mounted on the robot.
+///
+/// The GPS reading is the field position of the sensor itself, so both sides must apply the
+/// same mounting transform to convert between the robot's pose and the sensor's reading.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct GpsMount {
+    /// The sensor's offset from the robot's center of rotation in meters, with +x facing
+    /// the front of the robot and +y facing its left side.
+    pub offset: Point2<f64>,
+    /// The angle between the front of the robot and the direction the sensor faces, in degrees
+    /// clockwise.
+    pub heading_offset: f64,
+}
+
+/// A position and heading in the field coordinate system described by [`GpsField`].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct GpsPose {
+    /// The position of the sensor, in meters.
+    pub position: Point2<f64>,
+    /// The direction the sensor faces, in degrees clockwise from the +y axis.
+    pub heading: f64,
+}
+
+/// Low-level detail about how a GPS sensor arrived at its reading, for modeling degraded
+/// readings such as those close to a field wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct GpsDiagnostics {
+    /// The number of code strip segments in the sensor's view.
+    pub visible_segments: u16,
+    pub solution: GpsSolution,
+}
+
+/// Where a GPS sensor's reported pose comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum GpsSolution {
+    /// The sensor has not yet seen the code strips and is reporting its initial pose.
+    #[serde(rename = "Initial")]
+    Initial,
+    /// The sensor cannot see enough of the code strips and is estimating its pose from its
+    /// internal IMU since the last fix.
+    #[serde(rename = "DeadReckoning")]
+    DeadReckoning,
+    /// The sensor's pose was computed from the code strips in view.
+    #[serde(rename = "Fix")]
+    Fix,
+}
+
+/// How the field's GPS code strips are placed relative to the simulator's world coordinates.
+///
+/// GPS readings are reported in the field coordinate system defined by the code strips, which
+/// has its origin at the center of the field and measures headings in degrees clockwise from
+/// the +y axis.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct GpsField {
+    /// The location of the field's center in world coordinates, in meters.
+    pub center: Point2<f64>,
+    /// The rotation of the field coordinate system relative to the world coordinate system,
+    /// in degrees counterclockwise.
+    pub rotation: f64,
+}
+
+/// The direction an inertial sensor is mounted in, described by which of its axes points up.
+///
+/// The sensor's axes are remapped so that readings are reported as if the sensor were mounted
+/// flat with +Z up, matching the orientation modes of the V5 inertial sensor.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum ImuOrientation {
+    #[default]
+    #[serde(rename = "ZUp")]
+    ZUp,
+    #[serde(rename = "ZDown")]
+    ZDown,
+    #[serde(rename = "XUp")]
+    XUp,
+    #[serde(rename = "XDown")]
+    XDown,
+    #[serde(rename = "YUp")]
+    YUp,
+    #[serde(rename = "YDown")]
+    YDown,
+}
+
+/// A model of ADI potentiometer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PotentiometerKind {
+    /// The original potentiometer, which turns through 250 degrees.
+    #[serde(rename = "Legacy")]
+    Legacy,
+    /// The V2 potentiometer, which turns through 333 degrees.
+    #[serde(rename = "V2")]
+    V2,
+}
+
+impl PotentiometerKind {
+    /// The angle the potentiometer's shaft can turn through, in degrees.
+    pub fn max_angle(&self) -> f64 {
+        match self {
+            Self::Legacy => 250.0,
+            Self::V2 => 333.0,
+        }
+    }
+}
+
+/// Filtering applied to an ADI analog input before user code reads it.
+///
+/// The default is no filtering, meaning user code reads the latest voltage as soon as it
+/// changes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct AnalogFilter {
+    /// How often the input is sampled, in hertz, or `None` to sample on every read.
+    pub sample_rate: Option<f64>,
+    /// The number of most recent samples averaged into each reading. Values of 0 and 1 both
+    /// mean no averaging.
+    pub window: u16,
+}
+
+impl AnalogFilter {
+    /// Whether user code reads unfiltered values.
+    pub fn is_raw(&self) -> bool {
+        self.sample_rate.is_none() && self.window <= 1
+    }
+}
+
+/// The measurement range of an ADI accelerometer, selected with a jumper on the sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AccelerometerRange {
+    /// Measures up to ±2 g with higher precision.
+    #[serde(rename = "Low")]
+    Low,
+    /// Measures up to ±6 g.
+    #[serde(rename = "High")]
+    High,
+}
+
+impl AccelerometerRange {
+    /// The largest acceleration the sensor can measure along each axis, in g.
+    pub fn max_acceleration(&self) -> f64 {
+        match self {
+            Self::Low => 2.0,
+            Self::High => 6.0,
+        }
+    }
+}
+
+/// What a V5 radio is connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum RadioConnection {
+    /// The radio is searching for a link.
+    #[serde(rename = "Disconnected")]
+    Disconnected,
+    /// The radio is linked to a controller over VEXnet.
+    #[serde(rename = "VexNet")]
+    VexNet,
+    /// The radio is linked to a controller or device over Bluetooth.
+    #[serde(rename = "Bluetooth")]
+    Bluetooth,
+    /// The radio is linked for wireless program downloads, using the faster download channel.
+    #[serde(rename = "Download")]
+    Download,
+}
+
+/// Systematic errors in an inertial sensor's readings.
+///
+/// Whichever side generates the sensor's readings applies these errors, so that code which
+/// compensates for drift can be tested.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ImuDrift {
+    /// The rate the reported heading drifts away from the true heading, in degrees per minute.
+    pub heading_drift_rate: f64,
+    /// A constant offset added to the angular velocity about each axis, in degrees per second.
+    pub gyro_bias: [f64; 3],
+}
+
+/// The current state of a V5 peripheral.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum DeviceStatus {
+    #[serde(rename = "Motor")]
+    Motor {
+        /// The velocity of the motor in the direction set by `direction_configured`, so a
+        /// positive velocity means the motor is spinning the way user code asked it to.
+        velocity: f64,
+        /// The direction user code has configured as "forward" for this motor.
+        ///
+        /// Older implementations send this as a boolean `reversed` field.
+        #[serde(alias = "reversed", default)]
+        direction_configured: MotorDirection,
+        /// Whether the output shaft is physically spinning counterclockwise, regardless of the
+        /// configured direction.
+        #[serde(default)]
+        spinning_reverse: bool,
+        power_draw: f64,
+        torque_output: f64,
+        flags: i32,
+        position: f64,
+        target_position: f64,
+        voltage: f64,
+        gearset: MotorGearset,
+        brake_mode: MotorBrakeMode,
+        /// The raw encoder reading, or `None` unless the
+        /// [`ENCODER_TICKS`](crate::extensions::ENCODER_TICKS) extension was negotiated.
+        #[serde(default)]
+        encoder: Option<EncoderTicks>,
+        /// The temperature of the motor, in degrees Celsius.
+        #[serde(default)]
+        temperature: f64,
+        /// The current drawn by the motor, in amps.
+        #[serde(default)]
+        current: f64,
+        /// The motor's efficiency as a percentage from 0 to 100, where 100 means all input
+        /// power is turned into mechanical output.
+        #[serde(default)]
+        efficiency: f64,
+        #[serde(default)]
+        faults: MotorFaults,
+    },
+    /// Readings from an inertial sensor, reported as if it were mounted with +Z up.
+    #[serde(rename = "Imu")]
+    Imu {
+        /// The heading in degrees clockwise, wrapped to the range [0, 360).
+        heading: f64,
+        /// The total rotation in degrees clockwise, without wrapping.
+        rotation: f64,
+        quaternion: Quaternion<f64>,
+        euler: EulerAngles,
+        /// Angular velocity about each axis, in degrees per second.
+        gyro_rate: Vector3<f64>,
+        /// Linear acceleration along each axis, in g.
+        acceleration: Vector3<f64>,
+        /// Whether the sensor is calibrating, during which its readings are not valid.
+        calibrating: bool,
+    },
+    /// Readings from a rotation sensor.
+    #[serde(rename = "RotationSensor")]
+    RotationSensor {
+        /// The absolute angle of the shaft in degrees, from 0 to 360.
+        angle: f64,
+        /// The total rotation in degrees since the position was last reset.
+        position: f64,
+        /// The angular velocity, in degrees per second.
+        velocity: f64,
+        /// Whether user code has reversed the sensor's direction.
+        reversed: bool,
+        /// The raw encoder reading, or `None` unless the
+        /// [`ENCODER_TICKS`](crate::extensions::ENCODER_TICKS) extension was negotiated.
+        #[serde(default)]
+        encoder: Option<EncoderTicks>,
+    },
+    /// Readings from a distance sensor.
+    #[serde(rename = "Distance")]
+    Distance {
+        /// The distance to the nearest object in millimeters, or `None` if nothing is in range.
+        distance_mm: Option<u32>,
+        /// How confident the sensor is in its reading, from 0 to 63.
+        confidence: u8,
+        /// The apparent size of the detected object, from 0 to 400.
+        object_size: u32,
+        /// The rate at which the detected object is approaching, in meters per second.
+        object_velocity: f64,
+    },
+    /// Readings from an optical sensor.
+    #[serde(rename = "Optical")]
+    Optical {
+        /// The hue of the detected color in degrees, from 0 to 360.
+        hue: f64,
+        /// The saturation of the detected color, from 0.0 to 1.0.
+        saturation: f64,
+        /// The brightness of the detected color, from 0.0 to 1.0.
+        brightness: f64,
+        /// How close the nearest object is, from 0 (far) to 255 (close).
+        proximity: u8,
+        /// The brightness of the sensor's LED as a PWM duty cycle from 0 to 100.
+        led_pwm: u8,
+    },
+    /// The objects currently detected by a vision sensor, largest first.
+    #[serde(rename = "Vision")]
+    Vision { objects: Vec<VisionObject> },
+    /// The objects currently detected by an AI Vision sensor, grouped by detection class.
+    ///
+    /// Classes disabled in the sensor's configuration are always empty.
+    #[serde(rename = "AiVision")]
+    AiVision {
+        tags: Vec<AprilTagDetection>,
+        blobs: Vec<ColorBlob>,
+        objects: Vec<ModelDetection>,
+    },
+    /// Readings from a GPS sensor, in the field coordinate system described by [`GpsField`].
+    #[serde(rename = "Gps")]
+    Gps {
+        pose: GpsPose,
+        /// How confident the sensor is in its reading, as a percentage from 0 to 100.
+        quality: u8,
+        /// Detail about how the reading was computed, or `None` unless the
+        /// [`GPS_DIAGNOSTICS`](crate::extensions::GPS_DIAGNOSTICS) extension was negotiated.
+        #[serde(default)]
+        diagnostics: Option<GpsDiagnostics>,
+    },
+    /// The state of an electromagnet.
+    #[serde(rename = "Electromagnet")]
+    Electromagnet {
+        /// The power applied to the magnet, from -1.0 to 1.0. Negative power repels objects.
+        power: f64,
+        /// Whether the magnet is currently holding an object.
+        holding: bool,
+    },
+    /// The count of an ADI quadrature encoder, reported on its top port.
+    #[serde(rename = "AdiEncoder")]
+    AdiEncoder {
+        /// The number of ticks counted since the encoder was last reset, with 360 ticks per
+        /// revolution.
+        ticks: i32,
+    },
+    /// The reading of an ADI ultrasonic rangefinder, reported on its output port.
+    #[serde(rename = "AdiUltrasonic")]
+    AdiUltrasonic {
+        /// The distance to the nearest object in millimeters, or `None` if nothing is in range.
+        distance_mm: Option<u32>,
+    },
+    /// The reading of an ADI gyro.
+    #[serde(rename = "AdiGyro")]
+    AdiGyro {
+        /// The accumulated heading in degrees clockwise, without wrapping.
+        heading: f64,
+        /// Whether the gyro is calibrating, during which its heading is not valid.
+        calibrating: bool,
+    },
+    /// The reading of an ADI potentiometer.
+    #[serde(rename = "AdiPotentiometer")]
+    AdiPotentiometer {
+        /// The angle of the potentiometer's shaft in degrees, from 0 to the maximum angle of
+        /// its [`PotentiometerKind`].
+        angle: f64,
+    },
+    /// The reading of an ADI line tracker.
+    #[serde(rename = "AdiLineTracker")]
+    AdiLineTracker {
+        /// How much of the sensor's light is reflected back by the surface below it, from 0.0
+        /// (dark) to 1.0 (light).
+        reflectivity: f64,
+    },
+    /// The reading of an ADI accelerometer.
+    #[serde(rename = "AdiAccelerometer")]
+    AdiAccelerometer {
+        /// Acceleration along each axis in g, clamped to the sensor's range.
+        acceleration: Vector3<f64>,
+    },
+    /// The state of a pneumatic solenoid.
+    #[serde(rename = "AdiSolenoid")]
+    AdiSolenoid {
+        /// Whether the cylinders controlled by the solenoid are extended.
+        extended: bool,
+        /// The number of times the solenoid has switched state since the program started, which
+        /// frontends can use to estimate air usage.
+        actuations: u32,
+    },
+    /// The state of a V5 radio's link.
+    #[serde(rename = "Radio")]
+    Radio {
+        connection: RadioConnection,
+        /// The radio channel in use, or `None` when not connected.
+        channel: Option<u8>,
+        /// The quality of the received signal, as a percentage from 0 to 100.
+        signal_quality: u8,
+    },
+}
+
+/// A raw encoder reading, as returned by the SDK's tick-based position functions.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct EncoderTicks {
+    /// The encoder's position in ticks.
+    pub count: i32,
+    /// The time the reading was taken, in milliseconds since user code started.
+    pub timestamp_ms: u32,
+}
+
+/// The state of a motor's velocity controller at one control step.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct MotorSample {
+    /// The time of the control step, in microseconds since user code started.
+    pub timestamp_us: u64,
+    /// The velocity the controller is trying to reach, in RPM.
+    pub target_velocity: f64,
+    /// The measured velocity, in RPM.
+    pub velocity: f64,
+    /// The voltage the controller applied, in volts.
+    pub voltage: f64,
+}
+
+/// An orientation as pitch, roll, and yaw angles, in degrees.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct EulerAngles {
+    pub pitch: f64,
+    pub roll: f64,
+    pub yaw: f64,
+}
+
+/// The state of a light built into a V5 peripheral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum LedState {
+    /// The white illumination LED on an optical sensor.
+    #[serde(rename = "Optical")]
+    Optical {
+        /// LED brightness as a PWM duty cycle from 0 to 100.
+        brightness: u8,
+    },
+    /// The RGB LED on the front of a vision sensor.
+    #[serde(rename = "Vision")]
+    Vision { color: Color },
+    /// The status LED on a distance sensor, which is lit while an object is in range.
+    #[serde(rename = "Distance")]
+    Distance { lit: bool },
+}
+
+/// The gearset of a VEX V5 motor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MotorGearset {
+    #[serde(rename = "Red")]
+    Red,
+    #[serde(rename = "Green")]
+    Green,
+    #[serde(rename = "Blue")]
+    Blue,
+}
+
+/// The direction a VEX V5 motor treats as positive.
+///
+/// Deserializes from either a variant name or a boolean, where `true` means [`Reverse`].
+///
+/// [`Reverse`]: MotorDirection::Reverse
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum MotorDirection {
+    /// Positive values spin the output shaft clockwise.
+    #[default]
+    #[serde(rename = "Forward")]
+    Forward,
+    /// Positive values spin the output shaft counterclockwise.
+    #[serde(rename = "Reverse")]
+    Reverse,
+}
+
+impl MotorDirection {
+    pub fn is_reverse(&self) -> bool {
+        *self == Self::Reverse
+    }
+}
+
+impl<'de> Deserialize<'de> for MotorDirection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DirectionVisitor;
+
+        impl Visitor<'_> for DirectionVisitor {
+            type Value = MotorDirection;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("\"Forward\", \"Reverse\", or a boolean")
+            }
+
+            fn visit_bool<E: de::Error>(self, reversed: bool) -> Result<MotorDirection, E> {
+                Ok(if reversed {
+                    MotorDirection::Reverse
+                } else {
+                    MotorDirection::Forward
+                })
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<MotorDirection, E> {
+                match value {
+                    "Forward" => Ok(MotorDirection::Forward),
+                    "Reverse" => Ok(MotorDirection::Reverse),
+                    _ => Err(E::unknown_variant(value, &["Forward", "Reverse"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(DirectionVisitor)
+    }
+}
+
+/// The brake mode of a VEX V5 motor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum MotorBrakeMode {
+    #[serde(rename = "Coast")]
+    Coast,
+    #[serde(rename = "Brake")]
+    Brake,
+    #[serde(rename = "Hold")]
+    Hold,
+}
+
+/// The limits user code has placed on a VEX V5 motor's output.
+///
+/// The physics model should not let the motor draw more current or apply more voltage than
+/// these limits allow.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct MotorLimits {
+    /// The maximum current the motor may draw, in amps.
+    pub current_limit: f64,
+    /// The maximum voltage that may be applied to the motor, in volts.
+    pub voltage_limit: f64,
+}
+
+impl Default for MotorLimits {
+    /// The limits of a motor that user code has not configured: 2.5 A and 12 V.
+    fn default() -> Self {
+        Self {
+            current_limit: 2.5,
+            voltage_limit: 12.0,
+        }
+    }
+}
+
+/// Fault conditions reported by a VEX V5 motor.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct MotorFaults {
+    /// The motor is too hot, and is limiting its output to cool down.
+    pub over_temperature: bool,
+    /// The motor's H-bridge has faulted.
+    pub driver_fault: bool,
+    /// The motor is drawing more current than its limit.
+    pub over_current: bool,
+    /// The motor's H-bridge is drawing too much current.
+    pub driver_over_current: bool,
+}
+
+impl MotorFaults {
+    /// The faults as the bitmask returned by the V5 SDK's `vexDeviceMotorFaultsGet`.
+    pub fn bits(&self) -> u32 {
+        u32::from(self.over_temperature)
+            | u32::from(self.driver_fault) << 1
+            | u32::from(self.over_current) << 2
+            | u32::from(self.driver_over_current) << 3
+    }
+
+    /// Creates faults from a V5 SDK bitmask, ignoring unknown bits.
+    pub fn from_bits(bits: u32) -> Self {
+        Self {
+            over_temperature: bits & 0x01 != 0,
+            driver_fault: bits & 0x02 != 0,
+            over_current: bits & 0x04 != 0,
+            driver_over_current: bits & 0x08 != 0,
+        }
+    }
+}
+
+/// An arbitrary port on the VEX V5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Port {
+    #[serde(rename = "Smart")]
+    Smart(SmartPort),
+    #[serde(rename = "Adi")]
+    Adi(AdiPort),
+    /// A device built into the robot that is not connected to a port, addressed so that
+    /// device-oriented messages can refer to it like any other device.
+    #[serde(rename = "Internal")]
+    Internal(InternalDevice),
+    /// An ADI port on a 3-wire expander connected to the smart port `expander`.
+    #[serde(rename = "ExpanderAdi")]
+    ExpanderAdi { expander: SmartPort, port: AdiPort },
+}
+
+impl Port {
+    /// The ADI port `port` on the brain, or on the expander connected to `expander` if there is
+    /// one.
+    pub fn adi(expander: Option<SmartPort>, port: AdiPort) -> Self {
+        match expander {
+            Some(expander) => Self::ExpanderAdi { expander, port },
+            None => Self::Adi(port),
+        }
+    }
+}
+
+impl fmt::Display for Port {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Smart(SmartPort(port)) => write!(f, "Smart port {port}"),
+            Self::Adi(AdiPort(port)) => write!(f, "ADI port {port}"),
+            Self::ExpanderAdi {
+                expander: SmartPort(expander),
+                port: AdiPort(port),
+            } => write!(f, "ADI port {port} on expander {expander}"),
+            Self::Internal(InternalDevice::Battery) => f.write_str("Battery"),
+            Self::Internal(InternalDevice::Brain) => f.write_str("Brain"),
+            Self::Internal(InternalDevice::Controller(ControllerId::Primary)) => {
+                f.write_str("Primary controller")
+            }
+            Self::Internal(InternalDevice::Controller(ControllerId::Partner)) => {
+                f.write_str("Partner controller")
+            }
+        }
+    }
+}
+
+/// An RJ9 4p4c "Smart" port on the VEX V5.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SmartPort(pub u8);
+
+/// A 3-wire "ADI" port for analog devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AdiPort(pub u8);
+
+/// A device that is part of every robot rather than being plugged into a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InternalDevice {
+    #[serde(rename = "Battery")]
+    Battery,
+    #[serde(rename = "Brain")]
+    Brain,
+    #[serde(rename = "Controller")]
+    Controller(ControllerId),
+}
+
+/// Battery status and statistics.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Battery {
+    pub voltage: f64,
+    pub current: f64,
+    pub capacity: f64,
+    /// Detailed statistics, or `None` unless the
+    /// [`BATTERY_DETAILS`](crate::extensions::BATTERY_DETAILS) extension was negotiated.
+    #[serde(default)]
+    pub details: Option<BatteryDetails>,
+}
+
+/// Detailed battery statistics, like those reported by the battery's management chip.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct BatteryDetails {
+    pub state: ChargeState,
+    /// The number of full charge cycles the battery has been through.
+    pub cycle_count: u32,
+    /// The voltage of each cell, in volts.
+    pub cell_voltages: Vec<f64>,
+    /// The battery's temperature, in degrees Celsius.
+    pub temperature: f64,
+}
+
+/// Whether a battery is being charged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ChargeState {
+    #[serde(rename = "Discharging")]
+    Discharging,
+    #[serde(rename = "Charging")]
+    Charging,
+    /// The battery is connected to a charger but already full.
+    #[serde(rename = "Full")]
+    Full,
+}
+
+/// The fields of a [`DeviceStatus`] that changed, keyed by field name.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct StatusDelta(pub BTreeMap<String, FieldValue>);
+
+/// The new value of a single status field, in the same form it has in a full status.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<FieldValue>),
+    Object(BTreeMap<String, FieldValue>),
+}
@@ -0,0 +1,238 @@
+//! Canonical example messages, paired with their exact encodings.
+//!
+//! Implementations in other languages or built on other libraries can check that they encode
+//! and decode each example to and from exactly these bytes, instead of copying snippets from
+//! the documentation that may drift out of date.
+
+use mint::Point2;
+
+use crate::{
+    Color, Command, CompMode, CompetitionMode, ControllerState, ControllerUpdate, Device,
+    DeviceStatus, DrawCommand, Event, LogLevel, LogSource, MotorBrakeMode, MotorDirection,
+    MotorFaults, MotorGearset, Port, Rect, SerialData, Shape, SmartPort, TouchEvent,
+    PROTOCOL_VERSION,
+};
+
+/// A message and its canonical encoding as a single line of JSON, without the trailing
+/// newline.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Example<T> {
+    pub name: &'static str,
+    pub json: &'static str,
+    pub message: T,
+}
+
+/// Every example event.
+pub fn events() -> Vec<Example<Event>> {
+    vec![
+        handshake_event(),
+        screen_draw_fill(),
+        screen_clear(),
+        screen_render(),
+        serial_event(),
+        device_update_motor(),
+        log(),
+        exited(),
+    ]
+}
+
+/// Every example command.
+pub fn commands() -> Vec<Example<Command>> {
+    vec![
+        handshake_command(),
+        touch(),
+        controller_update(),
+        competition_mode(),
+        configure_device_motor(),
+        start_execution(),
+    ]
+}
+
+const SCREEN: Rect = Rect {
+    top_left: Point2 { x: 0, y: 0 },
+    bottom_right: Point2 { x: 479, y: 271 },
+};
+
+pub fn handshake_event() -> Example<Event> {
+    Example {
+        name: "handshake_event",
+        json: r#"{"Handshake":{"version":2,"extensions":[]}}"#,
+        message: Event::Handshake {
+            version: PROTOCOL_VERSION,
+            extensions: Vec::new(),
+        },
+    }
+}
+
+pub fn screen_draw_fill() -> Example<Event> {
+    Example {
+        name: "screen_draw_fill",
+        json: r#"{"ScreenDraw":{"command":{"Fill":{"shape":{"Rectangle":{"top_left":[10,20],"bottom_right":[50,60]}}}},"color":16711680,"clip_region":{"top_left":[0,0],"bottom_right":[479,271]}}}"#,
+        message: Event::ScreenDraw {
+            command: DrawCommand::Fill {
+                shape: Shape::Rectangle {
+                    top_left: Point2 { x: 10, y: 20 },
+                    bottom_right: Point2 { x: 50, y: 60 },
+                },
+            },
+            color: Color(0xFF0000),
+            clip_region: SCREEN,
+        },
+    }
+}
+
+pub fn screen_clear() -> Example<Event> {
+    Example {
+        name: "screen_clear",
+        json: r#"{"ScreenClear":{"color":0,"clip_region":{"top_left":[0,0],"bottom_right":[479,271]}}}"#,
+        message: Event::ScreenClear {
+            color: Color(0),
+            clip_region: SCREEN,
+        },
+    }
+}
+
+pub fn screen_render() -> Example<Event> {
+    Example {
+        name: "screen_render",
+        json: r#""ScreenRender""#,
+        message: Event::ScreenRender,
+    }
+}
+
+pub fn serial_event() -> Example<Event> {
+    Example {
+        name: "serial_event",
+        json: r#"{"Serial":{"channel":1,"data":"aGVsbG8K"}}"#,
+        message: Event::Serial(SerialData::new(1, b"hello\n")),
+    }
+}
+
+pub fn device_update_motor() -> Example<Event> {
+    Example {
+        name: "device_update_motor",
+        json: r#"{"DeviceUpdate":{"status":{"Motor":{"velocity":120.0,"direction_configured":"Forward","spinning_reverse":false,"power_draw":2.5,"torque_output":0.4,"flags":0,"position":360.0,"target_position":0.0,"voltage":6.0,"gearset":"Green","brake_mode":"Coast","encoder":null,"temperature":35.0,"current":0.8,"efficiency":60.0,"faults":{"over_temperature":false,"driver_fault":false,"over_current":false,"driver_over_current":false}}},"port":{"Smart":1}}}"#,
+        message: Event::DeviceUpdate {
+            status: DeviceStatus::Motor {
+                velocity: 120.0,
+                direction_configured: MotorDirection::Forward,
+                spinning_reverse: false,
+                power_draw: 2.5,
+                torque_output: 0.4,
+                flags: 0,
+                position: 360.0,
+                target_position: 0.0,
+                voltage: 6.0,
+                gearset: MotorGearset::Green,
+                brake_mode: MotorBrakeMode::Coast,
+                encoder: None,
+                temperature: 35.0,
+                current: 0.8,
+                efficiency: 60.0,
+                faults: MotorFaults::default(),
+            },
+            port: Port::Smart(SmartPort(1)),
+        },
+    }
+}
+
+pub fn log() -> Example<Event> {
+    Example {
+        name: "log",
+        json: r#"{"Log":{"level":"Info","message":"calibrated","source":"User","category":null,"color":null,"location":null,"count":1}}"#,
+        message: Event::Log {
+            level: LogLevel::Info,
+            message: "calibrated".to_string(),
+            source: LogSource::User,
+            category: None,
+            color: None,
+            location: None,
+            count: 1,
+        },
+    }
+}
+
+pub fn exited() -> Example<Event> {
+    Example {
+        name: "exited",
+        json: r#""Exited""#,
+        message: Event::Exited,
+    }
+}
+
+pub fn handshake_command() -> Example<Command> {
+    Example {
+        name: "handshake_command",
+        json: r#"{"Handshake":{"version":2,"extensions":["zstd_compression"],"frame_rate":60.0}}"#,
+        message: Command::Handshake {
+            version: PROTOCOL_VERSION,
+            extensions: vec![crate::extensions::ZSTD_COMPRESSION.to_string()],
+            frame_rate: Some(60.0),
+        },
+    }
+}
+
+pub fn touch() -> Example<Command> {
+    Example {
+        name: "touch",
+        json: r#"{"Touch":{"pos":[240,136],"event":"Pressed"}}"#,
+        message: Command::Touch {
+            pos: Point2 { x: 240, y: 136 },
+            event: TouchEvent::Pressed,
+        },
+    }
+}
+
+pub fn controller_update() -> Example<Command> {
+    Example {
+        name: "controller_update",
+        json: r#"{"ControllerUpdate":[{"Raw":{"axis1":0,"axis2":0,"axis3":127,"axis4":0,"button_l1":false,"button_l2":false,"button_r1":true,"button_r2":false,"button_up":false,"button_down":false,"button_left":false,"button_right":false,"button_x":false,"button_b":false,"button_y":false,"button_a":false,"button_sel":false,"battery_level":100,"button_all":false,"flags":0,"battery_capacity":100}},null]}"#,
+        message: Command::ControllerUpdate(
+            Some(ControllerUpdate::Raw(ControllerState {
+                axis3: 127,
+                button_r1: true,
+                battery_level: 100,
+                battery_capacity: 100,
+                ..ControllerState::default()
+            })),
+            None,
+        ),
+    }
+}
+
+pub fn competition_mode() -> Example<Command> {
+    Example {
+        name: "competition_mode",
+        json: r#"{"CompetitionMode":{"enabled":true,"connected":true,"mode":"Auto","is_competition":true}}"#,
+        message: Command::CompetitionMode(CompetitionMode {
+            enabled: true,
+            connected: true,
+            mode: CompMode::Auto,
+            is_competition: true,
+        }),
+    }
+}
+
+pub fn configure_device_motor() -> Example<Command> {
+    Example {
+        name: "configure_device_motor",
+        json: r#"{"ConfigureDevice":{"port":{"Smart":1},"device":{"Motor":{"physical_gearset":"Green","moment_of_inertia":0.01,"reversed":false,"external_ratio":1.0}}}}"#,
+        message: Command::ConfigureDevice {
+            port: Port::Smart(SmartPort(1)),
+            device: Device::Motor {
+                physical_gearset: MotorGearset::Green,
+                moment_of_inertia: 0.01,
+                reversed: false,
+                external_ratio: 1.0,
+            },
+        },
+    }
+}
+
+pub fn start_execution() -> Example<Command> {
+    Example {
+        name: "start_execution",
+        json: r#""StartExecution""#,
+        message: Command::StartExecution,
+    }
+}
@@ -0,0 +1,318 @@
+//! Converting recorded sessions into formats understood by existing analysis tools, such as
+//! trace viewers and spreadsheets.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    io::Write,
+};
+
+use serde_json::{json, Map, Value};
+
+use crate::{
+    codec::CodecError,
+    competition::{CompMode, CompetitionMode, CompetitionPhase},
+    delta,
+    devices::{DeviceStatus, Port},
+    recording::{Message, Recording},
+    Command, Event,
+};
+
+const PROCESS_ID: u32 = 1;
+const PROGRAM_TRACK: u32 = 1;
+const COMPETITION_TRACK: u32 = 2;
+const LOG_TRACK: u32 = 3;
+
+/// Writes a recording in the [Chrome trace event format], which can be opened in Perfetto or
+/// `chrome://tracing`.
+///
+/// The program's run and each competition mode are shown as spans, log messages as instant
+/// events, and the numeric fields of device updates, the robot's pose, and the battery as
+/// counter tracks.
+///
+/// [Chrome trace event format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+pub fn write_chrome_trace<W: Write>(
+    recording: &Recording,
+    mut writer: W,
+) -> Result<(), CodecError> {
+    let mut events = vec![
+        metadata(
+            "process_name",
+            None,
+            recording.header.field().unwrap_or("Robot"),
+        ),
+        metadata("thread_name", Some(PROGRAM_TRACK), "Program"),
+        metadata("thread_name", Some(COMPETITION_TRACK), "Competition"),
+        metadata("thread_name", Some(LOG_TRACK), "Log"),
+    ];
+    let mut statuses = BTreeMap::new();
+    let mut running = false;
+    let mut competition: Option<&'static str> = None;
+    // The phases of a running competition schedule that have yet to take effect.
+    let mut schedule = VecDeque::new();
+    let mut end_ms = 0;
+
+    for entry in &recording.entries {
+        let ts = entry.time_ms * 1000;
+        end_ms = entry.time_ms;
+        advance_schedule(&mut schedule, &mut events, &mut competition, entry.time_ms);
+        match &entry.message {
+            Message::Command(Command::StartExecution | Command::RunProgram { .. }) if !running => {
+                running = true;
+                events.push(span("B", "Running", PROGRAM_TRACK, ts));
+            }
+            Message::Command(Command::CompetitionMode(mode)) => {
+                schedule.clear();
+                switch_competition(&mut events, &mut competition, mode, ts);
+            }
+            Message::Command(Command::CompetitionSchedule(phases)) => {
+                schedule = CompetitionPhase::starts(phases)
+                    .into_iter()
+                    .map(|(start, mode)| (entry.time_ms.saturating_add(start), mode))
+                    .collect();
+                advance_schedule(&mut schedule, &mut events, &mut competition, entry.time_ms);
+            }
+            Message::Event(Event::Exited) if running => {
+                running = false;
+                events.push(span("E", "Running", PROGRAM_TRACK, ts));
+            }
+            Message::Event(Event::WatchdogExpired { timeout }) => {
+                events.push(instant(
+                    "Watchdog expired",
+                    PROGRAM_TRACK,
+                    ts,
+                    json!({ "timeout": timeout }),
+                ));
+            }
+            Message::Event(Event::Log {
+                level,
+                message,
+                source,
+                category,
+                location,
+                count,
+                ..
+            }) => {
+                events.push(instant(
+                    message,
+                    LOG_TRACK,
+                    ts,
+                    json!({
+                        "level": level,
+                        "source": source,
+                        "category": category,
+                        "location": location,
+                        "count": count,
+                    }),
+                ));
+            }
+            Message::Event(event) => {
+                if let Some((name, args)) = telemetry(event, &mut statuses)? {
+                    events.push(json!({
+                        "name": name,
+                        "ph": "C",
+                        "ts": ts,
+                        "pid": PROCESS_ID,
+                        "args": args,
+                    }));
+                }
+            }
+            Message::Command(_) => {}
+        }
+    }
+
+    // Close any spans still open when the recording ended.
+    let ts = end_ms * 1000;
+    if running {
+        events.push(span("E", "Running", PROGRAM_TRACK, ts));
+    }
+    if let Some(name) = competition {
+        events.push(span("E", name, COMPETITION_TRACK, ts));
+    }
+
+    serde_json::to_writer(
+        &mut writer,
+        &json!({ "traceEvents": events, "displayTimeUnit": "ms" }),
+    )?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Ends the current span on the competition track and begins one for `mode`, unless the mode
+/// has the same name.
+fn switch_competition(
+    events: &mut Vec<Value>,
+    competition: &mut Option<&'static str>,
+    mode: &CompetitionMode,
+    ts: u64,
+) {
+    let name = match (mode.enabled, mode.mode) {
+        (false, _) => "Disabled",
+        (true, CompMode::Auto) => "Autonomous",
+        (true, CompMode::Driver) => "Driver",
+    };
+    if *competition != Some(name) {
+        if let Some(previous) = competition.replace(name) {
+            events.push(span("E", previous, COMPETITION_TRACK, ts));
+        }
+        events.push(span("B", name, COMPETITION_TRACK, ts));
+    }
+}
+
+/// Switches to each phase of a competition schedule that takes effect by `time_ms`, at the
+/// time it takes effect.
+fn advance_schedule(
+    schedule: &mut VecDeque<(u64, CompetitionMode)>,
+    events: &mut Vec<Value>,
+    competition: &mut Option<&'static str>,
+    time_ms: u64,
+) {
+    while let Some((start_ms, mode)) = schedule.front().copied() {
+        if start_ms > time_ms {
+            break;
+        }
+        schedule.pop_front();
+        switch_competition(events, competition, &mode, start_ms * 1000);
+    }
+}
+
+fn metadata(name: &str, tid: Option<u32>, value: &str) -> Value {
+    json!({
+        "name": name,
+        "ph": "M",
+        "pid": PROCESS_ID,
+        "tid": tid.unwrap_or(0),
+        "args": { "name": value },
+    })
+}
+
+fn span(ph: &str, name: &str, tid: u32, ts: u64) -> Value {
+    json!({ "name": name, "ph": ph, "ts": ts, "pid": PROCESS_ID, "tid": tid })
+}
+
+fn instant(name: &str, tid: u32, ts: u64, args: Value) -> Value {
+    json!({
+        "name": name,
+        "ph": "i",
+        "s": "t",
+        "ts": ts,
+        "pid": PROCESS_ID,
+        "tid": tid,
+        "args": args,
+    })
+}
+
+/// Writes the telemetry in a recording as CSV, for analysis in spreadsheets or data frame
+/// libraries.
+///
+/// The output is in long form, with one row per value and the columns `time_ms`, `source`,
+/// `channel`, and `value`. Sources are device ports, `Pose`, and `Battery`, and channels are the
+/// numeric fields reported by each source.
+pub fn write_csv<W: Write>(recording: &Recording, mut writer: W) -> Result<(), CodecError> {
+    writeln!(writer, "time_ms,source,channel,value")?;
+    let mut statuses = BTreeMap::new();
+    for entry in &recording.entries {
+        let Message::Event(event) = &entry.message else {
+            continue;
+        };
+        let Some((source, fields)) = telemetry(event, &mut statuses)? else {
+            continue;
+        };
+        for (channel, value) in fields {
+            writeln!(
+                writer,
+                "{},{},{},{value}",
+                entry.time_ms,
+                csv_field(&source),
+                csv_field(&channel),
+            )?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote, or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The numeric fields reported by a telemetry source, keyed by channel.
+type Channels = Map<String, Value>;
+
+/// The source and channels of an event that reports telemetry, or `None` for other events.
+///
+/// `statuses` holds the latest status of each device, which device deltas are applied to so
+/// that they report every channel of the updated status. A delta for a device with no known
+/// status, or one that doesn't fit it, is skipped.
+fn telemetry(
+    event: &Event,
+    statuses: &mut BTreeMap<Port, DeviceStatus>,
+) -> Result<Option<(String, Channels)>, CodecError> {
+    let (name, value) = match event {
+        Event::DeviceUpdate { status, port } => {
+            statuses.insert(*port, status.clone());
+            (port.to_string(), serde_json::to_value(status)?)
+        }
+        Event::DeviceDelta { port, delta } => {
+            let Some(status) = statuses.get_mut(port) else {
+                return Ok(None);
+            };
+            let Ok(updated) = delta::apply(status, delta) else {
+                return Ok(None);
+            };
+            *status = updated;
+            (port.to_string(), serde_json::to_value(&*status)?)
+        }
+        Event::RobotPose { x, y } => ("Pose".to_string(), json!({ "x": x, "y": y })),
+        Event::Battery(battery) => ("Battery".to_string(), serde_json::to_value(battery)?),
+        _ => return Ok(None),
+    };
+    let mut fields = Map::new();
+    numeric_fields(&value, "", &mut fields);
+    Ok(Some((name, fields)))
+}
+
+/// Collects every number in a serialized value, keyed by its dotted path.
+///
+/// The variant name of an externally tagged enum is left out of the path, so motor statuses
+/// produce keys like `velocity` rather than `Motor.velocity`. Booleans are recorded as 0 or 1.
+fn numeric_fields(value: &Value, prefix: &str, out: &mut Map<String, Value>) {
+    let key = |name: &str| {
+        if prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{prefix}.{name}")
+        }
+    };
+    match value {
+        Value::Number(_) => {
+            out.insert(prefix.to_string(), value.clone());
+        }
+        Value::Bool(flag) => {
+            out.insert(prefix.to_string(), Value::from(u8::from(*flag)));
+        }
+        Value::Object(fields) if fields.len() == 1 && prefix.is_empty() => {
+            let (name, inner) = fields.iter().next().unwrap();
+            if inner.is_object() {
+                numeric_fields(inner, "", out);
+            } else {
+                numeric_fields(inner, name, out);
+            }
+        }
+        Value::Object(fields) => {
+            for (name, inner) in fields {
+                numeric_fields(inner, &key(name), out);
+            }
+        }
+        Value::Array(items) => {
+            for (index, inner) in items.iter().enumerate() {
+                numeric_fields(inner, &key(&index.to_string()), out);
+            }
+        }
+        Value::Null | Value::String(_) => {}
+    }
+}
@@ -0,0 +1,76 @@
+//! Names of optional protocol extensions.
+//!
+//! Extensions are advertised in the `extensions` list of the handshake messages. Messages that
+//! belong to an extension must only be sent once both sides have advertised it.
+
+/// Streaming camera frames from the frontend to a simulated vision sensor.
+pub const VISION_FRAMES: &str = "vision_frames";
+
+/// Reporting whether the simulated robot's configuration follows competition rules.
+pub const LEGALITY: &str = "legality";
+
+/// Compressing the message stream with zstd once the handshake is complete.
+pub const ZSTD_COMPRESSION: &str = "zstd_compression";
+
+/// Presenting the brain's home screen.
+///
+/// The simulator lists the installed programs with [`Event::ProgramSlots`] and waits for the
+/// frontend to pick one with [`Command::RunProgram`] instead of [`Command::StartExecution`].
+///
+/// [`Event::ProgramSlots`]: crate::Event::ProgramSlots
+/// [`Command::RunProgram`]: crate::Command::RunProgram
+/// [`Command::StartExecution`]: crate::Command::StartExecution
+pub const BRAIN_UI: &str = "brain_ui";
+
+/// Periodic rendering statistics from the frontend, sent with [`Command::FrameStats`].
+///
+/// [`Command::FrameStats`]: crate::Command::FrameStats
+pub const FRAME_STATS: &str = "frame_stats";
+
+/// Notifications raised by user code and shown by the frontend, sent with
+/// [`Event::Notification`].
+///
+/// [`Event::Notification`]: crate::Event::Notification
+pub const NOTIFICATIONS: &str = "notifications";
+
+/// Choosing an autonomous routine from a list published by the program, instead of using a
+/// menu drawn on the robot's screen.
+pub const AUTON_SELECTOR: &str = "auton_selector";
+
+/// Including raw encoder ticks and timestamps in motor and rotation sensor statuses, so that
+/// tick-based SDK functions return values consistent with the simulator's physics.
+pub const ENCODER_TICKS: &str = "encoder_ticks";
+
+/// Injecting failures into the simulated robot with [`Command::InjectFault`].
+///
+/// [`Command::InjectFault`]: crate::Command::InjectFault
+pub const FAULT_INJECTION: &str = "fault_injection";
+
+/// Sending only the changed fields of device statuses with [`Event::DeviceDelta`].
+///
+/// [`Event::DeviceDelta`]: crate::Event::DeviceDelta
+pub const DEVICE_DELTAS: &str = "device_deltas";
+
+/// Including code strip visibility and solution status in GPS readings, with
+/// [`GpsDiagnostics`].
+///
+/// [`GpsDiagnostics`]: crate::devices::GpsDiagnostics
+pub const GPS_DIAGNOSTICS: &str = "gps_diagnostics";
+
+/// Including charge state, cycle count, and per-cell voltages in battery updates, with
+/// [`BatteryDetails`].
+///
+/// [`BatteryDetails`]: crate::devices::BatteryDetails
+pub const BATTERY_DETAILS: &str = "battery_details";
+
+/// Additional displays drawn on by the simulator, such as debugging dashboards, created with
+/// [`Event::DisplayOpened`].
+///
+/// [`Event::DisplayOpened`]: crate::Event::DisplayOpened
+pub const EXTRA_DISPLAYS: &str = "extra_displays";
+
+/// Streaming every step of a motor's velocity controller with [`Event::MotorTelemetry`], for
+/// tuning dashboards.
+///
+/// [`Event::MotorTelemetry`]: crate::Event::MotorTelemetry
+pub const MOTOR_TELEMETRY: &str = "motor_telemetry";
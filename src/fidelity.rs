@@ -0,0 +1,61 @@
+//! Declarations of how faithfully a simulator models the real robot.
+//!
+//! Each level is ordered from least to most faithful, so a minimum requirement can be checked
+//! with a comparison.
+
+use serde::{Deserialize, Serialize};
+
+/// What a simulator actually simulates, sent so that frontends can tell users which results
+/// are trustworthy.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct Fidelity {
+    pub motor_physics: MotorPhysics,
+    /// Whether inertial sensor readings include simulated noise and drift.
+    pub imu_noise: bool,
+    pub timing: TimingFidelity,
+}
+
+impl Fidelity {
+    /// Whether every aspect of this fidelity is at least as faithful as `minimum`.
+    pub fn satisfies(&self, minimum: &Fidelity) -> bool {
+        self.motor_physics >= minimum.motor_physics
+            && self.imu_noise >= minimum.imu_noise
+            && self.timing >= minimum.timing
+    }
+}
+
+/// How motors respond to the voltages and velocities requested by user code.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum MotorPhysics {
+    /// Motors do not move.
+    #[default]
+    #[serde(rename = "None")]
+    None,
+    /// Motors reach their requested velocity immediately, ignoring load and inertia.
+    #[serde(rename = "Kinematic")]
+    Kinematic,
+    /// Motors are modeled with torque, current limits, and the inertia of their load.
+    #[serde(rename = "Dynamic")]
+    Dynamic,
+}
+
+/// How closely the timing of user code's tasks matches the V5 brain.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum TimingFidelity {
+    /// Tasks run as fast as the host allows, with no relation to real time.
+    #[default]
+    #[serde(rename = "Unpaced")]
+    Unpaced,
+    /// Tasks are paced to wall-clock time, but scheduling depends on the host.
+    #[serde(rename = "RealTime")]
+    RealTime,
+    /// Tasks are scheduled deterministically in simulated time, matching the brain's scheduler.
+    #[serde(rename = "Deterministic")]
+    Deterministic,
+}
@@ -0,0 +1,63 @@
+//! Geometric primitives shared by other parts of the protocol.
+//!
+//! # Coordinate conventions
+//!
+//! Screen coordinates, used by every screen event and by touch input, are integer pixel
+//! coordinates with the origin at the top-left corner of the screen and +y pointing down.
+//!
+//! Field and physics coordinates, used by robot poses and sensor readings, have +y pointing up.
+//! The helpers in this module convert between screen coordinates and a y-up coordinate system
+//! centered on the screen, which is the convention most physics and math code expects.
+
+use mint::Point2;
+use serde::{Deserialize, Serialize};
+
+use crate::screen::{SCREEN_HEIGHT, SCREEN_WIDTH};
+
+/// A rectangle in screen coordinates, with both corners inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Rect {
+    pub top_left: Point2<i32>,
+    pub bottom_right: Point2<i32>,
+}
+
+impl Rect {
+    /// Whether `other` lies entirely within this rectangle.
+    ///
+    /// Both corners of a rectangle are inclusive.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        self.top_left.x <= other.top_left.x
+            && self.top_left.y <= other.top_left.y
+            && self.bottom_right.x >= other.bottom_right.x
+            && self.bottom_right.y >= other.bottom_right.y
+    }
+}
+
+/// Converts a point in screen coordinates (top-left origin, y-down) to a point relative to the
+/// center of the screen with +y pointing up.
+pub fn screen_to_centered(point: Point2<i32>) -> Point2<f64> {
+    Point2 {
+        x: f64::from(point.x) - f64::from(SCREEN_WIDTH) / 2.0,
+        y: f64::from(SCREEN_HEIGHT) / 2.0 - f64::from(point.y),
+    }
+}
+
+/// Converts a point relative to the center of the screen with +y pointing up to the nearest
+/// pixel in screen coordinates (top-left origin, y-down).
+///
+/// This is the inverse of [`screen_to_centered`].
+pub fn centered_to_screen(point: Point2<f64>) -> Point2<i32> {
+    Point2 {
+        x: (point.x + f64::from(SCREEN_WIDTH) / 2.0).round() as i32,
+        y: (f64::from(SCREEN_HEIGHT) / 2.0 - point.y).round() as i32,
+    }
+}
+
+/// Mirrors a point across the x axis, converting between y-down and y-up coordinates that
+/// share an origin.
+pub fn flip_y<T: std::ops::Neg<Output = T>>(point: Point2<T>) -> Point2<T> {
+    Point2 {
+        x: point.x,
+        y: -point.y,
+    }
+}
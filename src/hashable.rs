@@ -0,0 +1,114 @@
+//! A wrapper that makes any message usable as a hash set or map key.
+
+use std::{
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+use serde::{ser::Error, Serialize};
+use serde_json::Number;
+
+use crate::{codec::CodecError, delta, devices::FieldValue};
+
+/// A message compared and hashed by its encoded form.
+///
+/// Many message types contain `f64` fields, which prevents them from implementing
+/// [`Eq`] and [`Hash`]. Wrapping a message in `Hashable` compares it by its JSON
+/// encoding instead, with floats that JSON can't represent written out rather than as
+/// `null`. Like `ordered-float`, this treats every NaN as equal to every other NaN, and
+/// negative zero as equal to zero, while `Some(f64::NAN)` stays distinct from `None`.
+#[derive(Debug, Clone)]
+pub struct Hashable<T> {
+    value: T,
+    key: String,
+}
+
+impl<T: Serialize> Hashable<T> {
+    /// Wraps a message, failing if it cannot be encoded.
+    pub fn new(value: T) -> Result<Self, CodecError> {
+        let fields = delta::to_field_value(&value).map_err(serde_json::Error::custom)?;
+        let mut key = String::new();
+        write_key(&fields, &mut key);
+        Ok(Self { value, key })
+    }
+}
+
+impl<T> Hashable<T> {
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// The encoded form this value is compared by: its JSON encoding with object keys sorted,
+    /// NaN written as `NaN`, infinities as `inf` and `-inf`, and negative zero as `0.0`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl<T> Deref for Hashable<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> PartialEq for Hashable<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Hashable<T> {}
+
+impl<T> Hash for Hashable<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+    }
+}
+
+/// Writes the key of an encoded value.
+fn write_key(value: &FieldValue, key: &mut String) {
+    match value {
+        FieldValue::Null => key.push_str("null"),
+        FieldValue::Bool(value) => key.push_str(if *value { "true" } else { "false" }),
+        FieldValue::Integer(value) => key.push_str(&value.to_string()),
+        FieldValue::Float(value) if value.is_nan() => key.push_str("NaN"),
+        FieldValue::Float(value) if *value == f64::INFINITY => key.push_str("inf"),
+        FieldValue::Float(value) if *value == f64::NEG_INFINITY => key.push_str("-inf"),
+        // Both zeros compare equal, so they share the positive zero's key.
+        FieldValue::Float(value) if *value == 0.0 => key.push_str("0.0"),
+        FieldValue::Float(value) => {
+            key.push_str(&Number::from_f64(*value).map_or_else(String::new, |n| n.to_string()));
+        }
+        FieldValue::String(value) => {
+            key.push_str(&serde_json::Value::from(value.as_str()).to_string())
+        }
+        FieldValue::Array(items) => {
+            key.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    key.push(',');
+                }
+                write_key(item, key);
+            }
+            key.push(']');
+        }
+        FieldValue::Object(fields) => {
+            key.push('{');
+            for (index, (name, field)) in fields.iter().enumerate() {
+                if index > 0 {
+                    key.push(',');
+                }
+                key.push_str(&serde_json::Value::from(name.as_str()).to_string());
+                key.push(':');
+                write_key(field, key);
+            }
+            key.push('}');
+        }
+    }
+}
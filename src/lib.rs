@@ -7,28 +7,89 @@
 //!
 //! The frontend sends [`Command`]s to the code executor to control the robot code environment, simulating changes in robot hardware (like controller input and LCD touch events) or competition phase.
 //!
+//! Messages can be read from and written to a stream with the [`codec`] module.
+//!
 //! The full protocol is documented at <https://internals.vexide.dev/simulators/protocol>.
 #![deny(rust_2018_compatibility, rust_2018_idioms, unsafe_code)]
 
 use base64::{prelude::*, DecodeError};
-use mint::Point2;
-use rgb::RGB8;
+use mint::{Point2, Vector2, Vector3};
 use serde::{Deserialize, Serialize};
-use std::{num::NonZeroU16, path::PathBuf};
+use std::{fmt, num::NonZeroU32, path::PathBuf};
+
+pub mod brain;
+pub mod coalesce;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod competition;
+pub mod config;
+pub mod controller;
+pub mod delta;
+pub mod devices;
+pub mod examples;
+#[cfg(feature = "codec")]
+pub mod export;
+pub mod extensions;
+pub mod fidelity;
+pub mod geometry;
+#[cfg(feature = "codec")]
+pub mod hashable;
+pub mod link;
+pub mod mock;
+pub mod playback;
+pub mod prelude;
+pub mod priority;
+#[cfg(feature = "codec")]
+pub mod proxy;
+pub mod recording;
+#[cfg(feature = "codec")]
+pub mod scenario;
+pub mod schema;
+pub mod screen;
+pub mod serial;
+pub mod session;
+pub mod state;
+pub mod stats;
+#[cfg(feature = "codec")]
+pub mod transport;
+pub mod v1;
+pub mod v2;
+pub mod vision;
+
+pub use brain::*;
+pub use competition::*;
+pub use controller::*;
+pub use devices::*;
+pub use fidelity::*;
+pub use geometry::*;
+pub use link::*;
+pub use mock::*;
+pub use screen::*;
+pub use serial::*;
+pub use vision::*;
+
+/// The protocol version implemented by the root [`Event`] and [`Command`] types.
+///
+/// Message types for each supported protocol version are available in versioned
+/// modules such as [`v1`], so that an implementation can speak several versions at once.
+pub const PROTOCOL_VERSION: i32 = v2::VERSION;
 
 /// A message sent from the simulator to the frontend.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum Event {
+    #[serde(rename = "Handshake")]
     Handshake {
         version: i32,
         extensions: Vec<String>,
     },
+    #[serde(rename = "ScreenDraw")]
     ScreenDraw {
         command: DrawCommand,
         color: Color,
         /// The region of the screen that may be mutated.
         clip_region: Rect,
     },
+    #[serde(rename = "ScreenScroll")]
     ScreenScroll {
         location: ScrollLocation,
         lines: i32,
@@ -36,127 +97,693 @@ pub enum Event {
         /// The region of the screen that may be mutated.
         clip_region: Rect,
     },
+    #[serde(rename = "ScreenClear")]
     ScreenClear {
         color: Color,
         /// The region of the screen that may be mutated.
         clip_region: Rect,
     },
-    ScreenDoubleBufferMode {
-        enable: bool,
-    },
+    #[serde(rename = "ScreenDoubleBufferMode")]
+    ScreenDoubleBufferMode { enable: bool },
+    #[serde(rename = "ScreenRender")]
     ScreenRender,
+    #[serde(rename = "VCodeSig")]
     VCodeSig(VCodeSig),
+    #[serde(rename = "Ready")]
     Ready,
+    #[serde(rename = "Exited")]
     Exited,
+    #[serde(rename = "Serial")]
     Serial(SerialData),
-    DeviceUpdate {
-        status: DeviceStatus,
-        port: Port,
-    },
+    #[serde(rename = "DeviceUpdate")]
+    DeviceUpdate { status: DeviceStatus, port: Port },
+    /// The state of a light built into a V5 peripheral has changed.
+    #[serde(rename = "DeviceLed")]
+    DeviceLed { port: SmartPort, led: LedState },
+    #[serde(rename = "Battery")]
     Battery(Battery),
-    RobotPose {
-        x: f64,
-        y: f64,
-    },
+    #[serde(rename = "RobotPose")]
+    RobotPose { x: f64, y: f64 },
+    #[serde(rename = "RobotState")]
     RobotState(RobotState),
+    #[serde(rename = "Log")]
     Log {
         level: LogLevel,
         message: String,
-    },
+        #[serde(default)]
+        source: LogSource,
+        /// A short label for grouping related messages, such as `"odometry"`.
+        #[serde(default)]
+        category: Option<String>,
+        /// A color the frontend should use when displaying the message.
+        #[serde(default)]
+        color: Option<Color>,
+        /// Where in the program the message was logged.
+        #[serde(default)]
+        location: Option<SourceLocation>,
+        /// The number of times this message was logged in a row, with no other log messages
+        /// in between. Other kinds of events may have been sent between the repetitions; see
+        /// [`coalesce::collapse_logs`].
+        #[serde(default = "one")]
+        count: u32,
+    },
+    #[serde(rename = "VEXLinkConnect")]
     VEXLinkConnect {
         port: SmartPort,
         id: String,
         mode: LinkMode,
         r#override: bool,
     },
-    VEXLinkDisconnect {
+    #[serde(rename = "VEXLinkDisconnect")]
+    VEXLinkDisconnect { port: SmartPort },
+    #[serde(rename = "TextMetricsRequest")]
+    TextMetricsRequest { text: V5Text },
+    /// Requests that the frontend use a physical controller from [`Command::AvailableControllers`]
+    /// for the given controller, or stop using one if `uuid` is `None`.
+    #[serde(rename = "SelectController")]
+    SelectController {
+        id: ControllerId,
+        uuid: Option<String>,
+    },
+    /// User code has changed the mounting offset it expects a GPS sensor to have.
+    #[serde(rename = "GpsMount")]
+    GpsMount { port: SmartPort, mount: GpsMount },
+    /// Asks the frontend to start streaming camera frames for a vision sensor, or to stop if
+    /// `config` is `None`.
+    ///
+    /// Part of the [`VISION_FRAMES`](extensions::VISION_FRAMES) extension.
+    #[serde(rename = "VisionStream")]
+    VisionStream {
         port: SmartPort,
+        config: Option<FrameStreamConfig>,
+    },
+    /// A limit set by [`Command::ConfigureWatchdog`] was exceeded.
+    ///
+    /// The simulator stops user code and sends [`Event::Exited`] after this event.
+    #[serde(rename = "WatchdogExpired")]
+    WatchdogExpired { timeout: WatchdogTimeout },
+    /// Reports whether the robot's configuration is competition legal, as seen by the simulator.
+    ///
+    /// Part of the [`LEGALITY`](extensions::LEGALITY) extension.
+    #[serde(rename = "LegalityReport")]
+    LegalityReport(LegalityReport),
+    /// An image of the screen, captured because of a trigger set by
+    /// [`Command::SetScreenshotTriggers`].
+    #[serde(rename = "Screenshot")]
+    Screenshot {
+        trigger: ScreenshotTrigger,
+        screenshot: Screenshot,
+    },
+    /// The result of a [`Command::ConfigureDevices`].
+    #[serde(rename = "DevicesConfigured")]
+    DevicesConfigured(ConfigureDevicesResult),
+    /// Totals for the whole run, sent just before [`Event::Exited`].
+    #[serde(rename = "RunSummary")]
+    RunSummary(RunSummary),
+    /// Lists the programs installed on the simulated brain.
+    ///
+    /// Sent after the handshake and whenever the installed programs change. Part of the
+    /// [`BRAIN_UI`](extensions::BRAIN_UI) extension.
+    #[serde(rename = "ProgramSlots")]
+    ProgramSlots(Vec<ProgramSlot>),
+    /// Declares what the simulator actually simulates. Sent once, after the handshake.
+    #[serde(rename = "Fidelity")]
+    Fidelity(Fidelity),
+    /// A resynchronization marker, sent periodically so that a reader that received a
+    /// malformed message can skip ahead to a known-good point in the stream.
+    ///
+    /// `seq` increases by one with each marker the simulator sends. Markers carry no state and
+    /// may be ignored.
+    #[serde(rename = "Sync")]
+    Sync { seq: u64 },
+    /// A message from user code that the frontend should bring to the user's attention, such as
+    /// with a toast or dialog.
+    ///
+    /// Part of the [`NOTIFICATIONS`](extensions::NOTIFICATIONS) extension.
+    #[serde(rename = "Notification")]
+    Notification(Notification),
+    /// Lists the autonomous routines the program can run, sent before the frontend sends
+    /// [`Command::StartExecution`].
+    ///
+    /// Part of the [`AUTON_SELECTOR`](extensions::AUTON_SELECTOR) extension.
+    #[serde(rename = "AutonRoutines")]
+    AutonRoutines(Vec<AutonRoutine>),
+    /// The state user code is driving an ADI digital output to.
+    #[serde(rename = "AdiOutput")]
+    AdiOutput { port: Port, high: bool },
+    /// The fields of a device's status that changed since its previous update.
+    ///
+    /// Only sent once the [`DEVICE_DELTAS`](extensions::DEVICE_DELTAS) extension is
+    /// negotiated, and only after a full [`Event::DeviceUpdate`] of the same variant has been
+    /// sent for the port. See the [`delta`] module for helpers that produce and apply deltas.
+    #[serde(rename = "DeviceDelta")]
+    DeviceDelta { port: Port, delta: StatusDelta },
+    /// The colors user code has set on an addressable LED strip, in order from the LED nearest
+    /// the port.
+    #[serde(rename = "AdiLedBuffer")]
+    AdiLedBuffer { port: Port, colors: Vec<Color> },
+    /// How user code reads touches on the screen, sent after the program starts and whenever it
+    /// changes. Frontends can use this to explain why touches appear to be ignored.
+    #[serde(rename = "TouchListening")]
+    TouchListening(TouchListening),
+    /// The PWM value user code is driving an ADI servo or motor with.
+    #[serde(rename = "AdiPwmOutput")]
+    AdiPwmOutput {
+        port: Port,
+        /// The commanded output, from -127 to 127. For a servo this is its position, with the
+        /// extremes at either end of its travel and 0 centered. For a motor it is the duty cycle,
+        /// with the sign giving the direction.
+        value: i8,
+    },
+    /// User code has created a device object on a port, independent of what the frontend has
+    /// configured there.
+    ///
+    /// `kind` is the name of the [`Device`] variant user code expects, as returned by
+    /// [`Device::kind`], so frontends can highlight ports where it differs from the configured
+    /// device.
+    #[serde(rename = "PortClaimed")]
+    PortClaimed { port: Port, kind: String },
+    /// The voltage user code is driving an ADI analog output to, in volts.
+    #[serde(rename = "AdiAnalogOutput")]
+    AdiAnalogOutput { port: Port, voltage: f64 },
+    /// A new display has been created, and may be drawn on by later events.
+    ///
+    /// Part of the [`EXTRA_DISPLAYS`](extensions::EXTRA_DISPLAYS) extension.
+    #[serde(rename = "DisplayOpened")]
+    DisplayOpened(ExtraDisplay),
+    /// A display has been removed and will receive no more events.
+    #[serde(rename = "DisplayClosed")]
+    DisplayClosed { display: u32 },
+    /// Like [`Event::ScreenDraw`], but for a display created by [`Event::DisplayOpened`].
+    #[serde(rename = "DisplayDraw")]
+    DisplayDraw {
+        display: u32,
+        command: DrawCommand,
+        color: Color,
+        /// The region of the display that may be mutated.
+        clip_region: Rect,
     },
-    TextMetricsRequest {
-        text: V5Text,
+    /// Like [`Event::ScreenClear`], but for a display created by [`Event::DisplayOpened`].
+    #[serde(rename = "DisplayClear")]
+    DisplayClear {
+        display: u32,
+        color: Color,
+        /// The region of the display that may be mutated.
+        clip_region: Rect,
+    },
+    /// Presents everything drawn on a display since its previous render. Frontends should not
+    /// show a display's changes until this event is received.
+    #[serde(rename = "DisplayRender")]
+    DisplayRender { display: u32 },
+    /// Bytes written by user code to a smart port in generic serial mode.
+    #[serde(rename = "PortSerial")]
+    PortSerial(PortSerialData),
+    /// The simulator refused the frontend's handshake, and will close the connection.
+    ///
+    /// Sent in place of [`Event::Handshake`].
+    #[serde(rename = "HandshakeRejected")]
+    HandshakeRejected(HandshakeRejection),
+    /// User code has changed a motor's current or voltage limit.
+    ///
+    /// Motors use [`MotorLimits::default`] until this is sent.
+    #[serde(rename = "MotorLimits")]
+    MotorLimits {
+        port: SmartPort,
+        limits: MotorLimits,
+    },
+    /// Samples of a motor's velocity controller, taken since the previous telemetry event for
+    /// the motor, in the order they were taken.
+    ///
+    /// Only sent for motors requested with [`Command::SetMotorTelemetry`]. Part of the
+    /// [`MOTOR_TELEMETRY`](extensions::MOTOR_TELEMETRY) extension.
+    #[serde(rename = "MotorTelemetry")]
+    MotorTelemetry {
+        port: SmartPort,
+        samples: Vec<MotorSample>,
     },
 }
 
 /// A message sent from the frontend to the simulator.
 #[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum Command {
+    #[serde(rename = "Handshake")]
     Handshake {
         version: i32,
         extensions: Vec<String>,
-    },
-    Touch {
-        pos: Point2<i32>,
-        event: TouchEvent,
-    },
+        /// The rate at which the frontend redraws the screen, in frames per second.
+        ///
+        /// Simulators that batch screen events until [`Event::ScreenRender`] can use this to
+        /// align their flushes with the frontend's frames.
+        #[serde(default)]
+        frame_rate: Option<f64>,
+    },
+    #[serde(rename = "Touch")]
+    Touch { pos: Point2<i32>, event: TouchEvent },
+    #[serde(rename = "ControllerUpdate")]
     ControllerUpdate(Option<ControllerUpdate>, Option<ControllerUpdate>),
-    USD {
-        root: Option<PathBuf>,
-    },
-    VEXLinkOpened {
-        port: SmartPort,
-        mode: LinkMode,
-    },
-    VEXLinkClosed {
-        port: SmartPort,
-    },
+    #[serde(rename = "USD")]
+    USD { root: Option<PathBuf> },
+    #[serde(rename = "VEXLinkOpened")]
+    VEXLinkOpened { port: SmartPort, mode: LinkMode },
+    #[serde(rename = "VEXLinkClosed")]
+    VEXLinkClosed { port: SmartPort },
+    #[serde(rename = "CompetitionMode")]
     CompetitionMode(CompetitionMode),
-    ConfigureDevice {
-        port: Port,
-        device: Device,
-    },
+    /// Runs through a sequence of competition modes, each for a fixed time, as field control
+    /// does during a match.
+    ///
+    /// The simulator applies each phase's mode in turn, exactly as if it had been sent with
+    /// [`Command::CompetitionMode`], and stays in the last phase's mode once the schedule
+    /// ends. A later `CompetitionMode` or `CompetitionSchedule` command cancels the rest of the
+    /// schedule.
+    #[serde(rename = "CompetitionSchedule")]
+    CompetitionSchedule(Vec<CompetitionPhase>),
+    #[serde(rename = "ConfigureDevice")]
+    ConfigureDevice { port: Port, device: Device },
+    #[serde(rename = "AdiInput")]
     AdiInput {
         port: AdiPort,
         voltage: f64,
-    },
+        /// The smart port of the ADI expander that `port` belongs to, or `None` for the brain's
+        /// own ADI ports.
+        ///
+        /// `port` predates [`Port::ExpanderAdi`] and stays a bare [`AdiPort`] to keep this
+        /// command compatible with older peers. Use [`Port::adi`] to combine the two.
+        #[serde(default)]
+        expander: Option<SmartPort>,
+    },
+    #[serde(rename = "StartExecution")]
     StartExecution,
-    SetBatteryCapacity {
-        capacity: f64,
+    #[serde(rename = "SetBatteryCapacity")]
+    SetBatteryCapacity { capacity: f64 },
+    #[serde(rename = "SetTextMetrics")]
+    SetTextMetrics { text: V5Text, metrics: TextMetrics },
+    #[serde(rename = "Serial")]
+    Serial(SerialData),
+    /// Sets the simulated real-time clock, in milliseconds since the Unix epoch.
+    #[serde(rename = "SetSystemTime")]
+    SetSystemTime { unix_ms: u64 },
+    /// Lists the physical controllers currently connected to the frontend.
+    #[serde(rename = "AvailableControllers")]
+    AvailableControllers(Vec<PhysicalController>),
+    /// Sets the position of the GPS code strips used to compute GPS readings.
+    #[serde(rename = "SetGpsField")]
+    SetGpsField(GpsField),
+    /// A camera frame for a vision sensor.
+    ///
+    /// Frames may be sent at a lower resolution or rate than requested by [`Event::VisionStream`].
+    /// Part of the [`VISION_FRAMES`](extensions::VISION_FRAMES) extension.
+    #[serde(rename = "VisionFrame")]
+    VisionFrame { port: SmartPort, frame: VisionFrame },
+    /// Sets the position reported by a motor's or rotation sensor's encoder, in the same units as
+    /// the device's status, without otherwise reconfiguring the device.
+    #[serde(rename = "SetEncoderPosition")]
+    SetEncoderPosition { port: SmartPort, position: f64 },
+    /// Configures limits on how long the simulator may run user code.
+    #[serde(rename = "ConfigureWatchdog")]
+    ConfigureWatchdog(WatchdogConfig),
+    /// Reports whether the robot's configuration is competition legal, as seen by the frontend.
+    ///
+    /// Part of the [`LEGALITY`](extensions::LEGALITY) extension.
+    #[serde(rename = "LegalityReport")]
+    LegalityReport(LegalityReport),
+    /// Changes the connection that carries serial data.
+    #[serde(rename = "SetSerialPath")]
+    SetSerialPath { path: SerialPath },
+    /// Sets the situations in which the simulator sends [`Event::Screenshot`].
+    #[serde(rename = "SetScreenshotTriggers")]
+    SetScreenshotTriggers { triggers: Vec<ScreenshotTrigger> },
+    /// Delays the delivery of a device's status to user code by `micros` microseconds.
+    #[serde(rename = "SetDeviceLatency")]
+    SetDeviceLatency { port: Port, micros: u32 },
+    /// Configures several devices at once.
+    ///
+    /// The configurations are applied atomically: either every device is configured or none
+    /// are. Must be sent before [`Command::StartExecution`]. The simulator replies with
+    /// [`Event::DevicesConfigured`].
+    #[serde(rename = "ConfigureDevices")]
+    ConfigureDevices(Vec<DeviceConfig>),
+    /// Highlights a program slot on the brain's home screen without running it.
+    ///
+    /// Part of the [`BRAIN_UI`](extensions::BRAIN_UI) extension.
+    #[serde(rename = "SelectProgram")]
+    SelectProgram { slot: u8 },
+    /// Runs the program in a slot, taking the place of [`Command::StartExecution`].
+    ///
+    /// Part of the [`BRAIN_UI`](extensions::BRAIN_UI) extension.
+    #[serde(rename = "RunProgram")]
+    RunProgram { slot: u8 },
+    /// Stops the running program and returns to the brain's home screen.
+    ///
+    /// The simulator sends [`Event::Exited`] once the program has stopped. Part of the
+    /// [`BRAIN_UI`](extensions::BRAIN_UI) extension.
+    #[serde(rename = "StopProgram")]
+    StopProgram,
+    /// A resynchronization marker, sent periodically so that a reader that received a
+    /// malformed message can skip ahead to a known-good point in the stream.
+    ///
+    /// `seq` increases by one with each marker the frontend sends. Markers carry no state and
+    /// may be ignored.
+    #[serde(rename = "Sync")]
+    Sync { seq: u64 },
+    /// Declares the processing the frontend applies to a controller's joystick axes.
+    ///
+    /// Frontends that send raw axis values need not send this. Simulators that require raw
+    /// values may reject other processing with a [`LogLevel::Error`] log message.
+    #[serde(rename = "SetInputProcessing")]
+    SetInputProcessing {
+        controller: ControllerId,
+        processing: InputProcessing,
+    },
+    /// Reports how well the frontend has kept up with rendering since its previous report.
+    ///
+    /// Sent periodically, typically about once per second. Simulators may reduce the rate of
+    /// screen events while the frontend is falling behind. Part of the
+    /// [`FRAME_STATS`](extensions::FRAME_STATS) extension.
+    #[serde(rename = "FrameStats")]
+    FrameStats(FrameStats),
+    /// Chooses the autonomous routine the program will run, by its [`AutonRoutine::id`].
+    ///
+    /// Must be sent before [`Command::StartExecution`]. Part of the
+    /// [`AUTON_SELECTOR`](extensions::AUTON_SELECTOR) extension.
+    #[serde(rename = "SelectAutonRoutine")]
+    SelectAutonRoutine { id: String },
+    /// Sets what a distance sensor detects, as computed by the frontend's physics scene.
+    ///
+    /// The simulator derives the rest of the sensor's [`DeviceStatus::Distance`] from
+    /// successive inputs.
+    #[serde(rename = "DistanceInput")]
+    DistanceInput {
+        port: SmartPort,
+        /// The distance to the nearest object in millimeters, or `None` if nothing is in range.
+        distance_mm: Option<u32>,
+        /// The apparent size of the detected object, from 0 to 400.
+        object_size: u32,
+    },
+    /// Pushes the robot, such as to simulate being rammed by an opponent.
+    ///
+    /// `force` is in newtons in robot coordinates, with +x facing the front of the robot and +y
+    /// facing its left side. `torque` is in newton-meters, counterclockwise. Both are applied
+    /// at the robot's center of rotation for `duration_ms` milliseconds.
+    #[serde(rename = "ApplyDisturbance")]
+    ApplyDisturbance {
+        force: Vector2<f64>,
+        torque: f64,
+        duration_ms: u32,
+    },
+    /// Installs a generator that the simulator applies to a device input every tick, or removes
+    /// it if `generator` is `None`.
+    ///
+    /// `channel` names the input being driven, using the field name from the command that
+    /// would otherwise set it, such as `voltage` for [`Command::AdiInput`] or `distance_mm` for
+    /// [`Command::DistanceInput`]. Inputs sent by the frontend replace the generator's value
+    /// until its next tick.
+    #[serde(rename = "SetValueGenerator")]
+    SetValueGenerator {
+        port: Port,
+        channel: String,
+        generator: Option<ValueGenerator>,
+    },
+    /// Sets the objects a vision sensor detects, bypassing its image processing.
+    ///
+    /// The simulator reports these objects in the sensor's [`DeviceStatus::Vision`] until the
+    /// next detections are sent.
+    #[serde(rename = "VisionDetections")]
+    VisionDetections {
+        port: SmartPort,
+        objects: Vec<VisionObject>,
+    },
+    /// Makes part of the simulated robot fail, for testing how user code handles errors.
+    ///
+    /// The fault lasts for `duration_ms` milliseconds, or until user code exits if `None`.
+    /// Part of the [`FAULT_INJECTION`](extensions::FAULT_INJECTION) extension.
+    #[serde(rename = "InjectFault")]
+    InjectFault {
+        fault: Fault,
+        duration_ms: Option<u64>,
+    },
+    /// Sets the reading of a GPS sensor, for frontends whose physics scene computes GPS readings
+    /// itself instead of leaving it to the simulator.
+    #[serde(rename = "GpsInput")]
+    GpsInput {
+        port: SmartPort,
+        pose: GpsPose,
+        quality: u8,
+        /// Detail about how the reading was computed, if the frontend models it. Only sent once
+        /// the [`GPS_DIAGNOSTICS`](extensions::GPS_DIAGNOSTICS) extension is negotiated.
+        #[serde(default)]
+        diagnostics: Option<GpsDiagnostics>,
+    },
+    /// Turns an ADI quadrature encoder by `ticks`, which may be negative.
+    #[serde(rename = "AdvanceAdiEncoder")]
+    AdvanceAdiEncoder {
+        /// The encoder's top port.
+        port: Port,
+        ticks: i32,
     },
-    SetTextMetrics {
-        text: V5Text,
-        metrics: TextMetrics,
+    /// Sets the distance an ADI ultrasonic rangefinder detects.
+    #[serde(rename = "UltrasonicInput")]
+    UltrasonicInput {
+        /// The rangefinder's output port.
+        port: Port,
+        /// The distance to the nearest object in millimeters, or `None` if nothing is in range.
+        distance_mm: Option<u32>,
+    },
+    /// Sets the reflectivity of the surface below an ADI line tracker, such as from a map of
+    /// the field's tape lines.
+    #[serde(rename = "LineTrackerInput")]
+    LineTrackerInput {
+        port: Port,
+        /// The surface's reflectivity, from 0.0 (dark) to 1.0 (light).
+        reflectivity: f64,
+    },
+    /// Sets the acceleration measured by an ADI accelerometer, in g along each axis.
+    #[serde(rename = "AccelerometerInput")]
+    AccelerometerInput {
+        /// The accelerometer's X axis port.
+        port: Port,
+        acceleration: Vector3<f64>,
+    },
+    /// Bytes sent to user code by the device on a smart port in generic serial mode.
+    #[serde(rename = "PortSerial")]
+    PortSerial(PortSerialData),
+    /// Sets the filtering applied to an ADI analog input, such as a line tracker or
+    /// potentiometer, so that both sides agree on the values user code reads.
+    ///
+    /// Inputs are unfiltered until this is sent.
+    #[serde(rename = "SetAnalogFilter")]
+    SetAnalogFilter { port: Port, filter: AnalogFilter },
+    /// The frontend refused the simulator's handshake, and will close the connection.
+    #[serde(rename = "HandshakeRejected")]
+    HandshakeRejected(HandshakeRejection),
+    /// Starts sending [`Event::MotorTelemetry`] for a motor, including one sample every
+    /// `decimation` control steps, or stops if `decimation` is `None`.
+    ///
+    /// A decimation of 1 samples every control step. Simulators may batch samples into fewer
+    /// events, but should not drop them.
+    #[serde(rename = "SetMotorTelemetry")]
+    SetMotorTelemetry {
+        port: SmartPort,
+        decimation: Option<NonZeroU32>,
     },
-    Serial(SerialData),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct SerialData {
-    pub channel: u32,
-    pub data: String,
-}
+impl Event {
+    /// A message logged by the simulator at `level`, with no other details.
+    pub fn log(level: LogLevel, message: impl Into<String>) -> Self {
+        Self::Log {
+            level,
+            message: message.into(),
+            source: LogSource::Simulator,
+            category: None,
+            color: None,
+            location: None,
+            count: 1,
+        }
+    }
 
-impl SerialData {
-    pub fn new(channel: u32, bytes: &[u8]) -> Self {
-        Self {
-            channel,
-            data: BASE64_STANDARD.encode(bytes),
+    /// An informational message logged by the simulator.
+    pub fn log_info(message: impl Into<String>) -> Self {
+        Self::log(LogLevel::Info, message)
+    }
+
+    /// A warning logged by the simulator.
+    pub fn log_warn(message: impl Into<String>) -> Self {
+        Self::log(LogLevel::Warn, message)
+    }
+
+    /// An error logged by the simulator.
+    pub fn log_error(message: impl Into<String>) -> Self {
+        Self::log(LogLevel::Error, message)
+    }
+
+    /// The name of this event's variant, as it appears on the wire.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Handshake { .. } => "Handshake",
+            Self::ScreenDraw { .. } => "ScreenDraw",
+            Self::ScreenScroll { .. } => "ScreenScroll",
+            Self::ScreenClear { .. } => "ScreenClear",
+            Self::ScreenDoubleBufferMode { .. } => "ScreenDoubleBufferMode",
+            Self::ScreenRender => "ScreenRender",
+            Self::VCodeSig(_) => "VCodeSig",
+            Self::Ready => "Ready",
+            Self::Exited => "Exited",
+            Self::Serial(_) => "Serial",
+            Self::DeviceUpdate { .. } => "DeviceUpdate",
+            Self::DeviceLed { .. } => "DeviceLed",
+            Self::Battery(_) => "Battery",
+            Self::RobotPose { .. } => "RobotPose",
+            Self::RobotState(_) => "RobotState",
+            Self::Log { .. } => "Log",
+            Self::VEXLinkConnect { .. } => "VEXLinkConnect",
+            Self::VEXLinkDisconnect { .. } => "VEXLinkDisconnect",
+            Self::TextMetricsRequest { .. } => "TextMetricsRequest",
+            Self::SelectController { .. } => "SelectController",
+            Self::GpsMount { .. } => "GpsMount",
+            Self::VisionStream { .. } => "VisionStream",
+            Self::WatchdogExpired { .. } => "WatchdogExpired",
+            Self::LegalityReport(_) => "LegalityReport",
+            Self::Screenshot { .. } => "Screenshot",
+            Self::DevicesConfigured(_) => "DevicesConfigured",
+            Self::RunSummary(_) => "RunSummary",
+            Self::ProgramSlots(_) => "ProgramSlots",
+            Self::Fidelity(_) => "Fidelity",
+            Self::Sync { .. } => "Sync",
+            Self::Notification(_) => "Notification",
+            Self::AutonRoutines(_) => "AutonRoutines",
+            Self::AdiOutput { .. } => "AdiOutput",
+            Self::DeviceDelta { .. } => "DeviceDelta",
+            Self::AdiLedBuffer { .. } => "AdiLedBuffer",
+            Self::TouchListening(_) => "TouchListening",
+            Self::AdiPwmOutput { .. } => "AdiPwmOutput",
+            Self::PortClaimed { .. } => "PortClaimed",
+            Self::AdiAnalogOutput { .. } => "AdiAnalogOutput",
+            Self::DisplayOpened(_) => "DisplayOpened",
+            Self::DisplayClosed { .. } => "DisplayClosed",
+            Self::DisplayDraw { .. } => "DisplayDraw",
+            Self::DisplayClear { .. } => "DisplayClear",
+            Self::DisplayRender { .. } => "DisplayRender",
+            Self::PortSerial(_) => "PortSerial",
+            Self::HandshakeRejected(_) => "HandshakeRejected",
+            Self::MotorLimits { .. } => "MotorLimits",
+            Self::MotorTelemetry { .. } => "MotorTelemetry",
         }
     }
 
-    pub fn to_bytes(&self) -> Result<Vec<u8>, DecodeError> {
-        BASE64_STANDARD.decode(&self.data)
+    /// The combined length of the base64-encoded payloads in this event, in bytes.
+    pub fn payload_len(&self) -> usize {
+        match self {
+            Self::VCodeSig(sig) => sig.0.len(),
+            Self::Serial(data) => data.data.len(),
+            Self::ScreenDraw {
+                command: DrawCommand::CopyBuffer { buffer, .. },
+                ..
+            } => buffer.len(),
+            Self::DisplayDraw {
+                command: DrawCommand::CopyBuffer { buffer, .. },
+                ..
+            } => buffer.len(),
+            Self::Screenshot { screenshot, .. } => screenshot.buffer.len(),
+            Self::PortSerial(data) => data.data.len(),
+            _ => 0,
+        }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct CompetitionMode {
-    pub enabled: bool,
-    pub connected: bool,
-    pub mode: CompMode,
-    pub is_competition: bool,
-}
+impl Command {
+    /// A touch starting at `(x, y)` on the screen.
+    pub fn touch_press(x: i32, y: i32) -> Self {
+        Self::touch(x, y, TouchEvent::Pressed)
+    }
+
+    /// A touch held at `(x, y)` on the screen, such as while dragging.
+    pub fn touch_hold(x: i32, y: i32) -> Self {
+        Self::touch(x, y, TouchEvent::Held)
+    }
+
+    /// A touch ending at `(x, y)` on the screen.
+    pub fn touch_release(x: i32, y: i32) -> Self {
+        Self::touch(x, y, TouchEvent::Released)
+    }
+
+    fn touch(x: i32, y: i32, event: TouchEvent) -> Self {
+        Self::Touch {
+            pos: Point2 { x, y },
+            event,
+        }
+    }
+
+    /// The name of this command's variant, as it appears on the wire.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Handshake { .. } => "Handshake",
+            Self::Touch { .. } => "Touch",
+            Self::ControllerUpdate(..) => "ControllerUpdate",
+            Self::USD { .. } => "USD",
+            Self::VEXLinkOpened { .. } => "VEXLinkOpened",
+            Self::VEXLinkClosed { .. } => "VEXLinkClosed",
+            Self::CompetitionMode(_) => "CompetitionMode",
+            Self::CompetitionSchedule(_) => "CompetitionSchedule",
+            Self::ConfigureDevice { .. } => "ConfigureDevice",
+            Self::AdiInput { .. } => "AdiInput",
+            Self::StartExecution => "StartExecution",
+            Self::SetBatteryCapacity { .. } => "SetBatteryCapacity",
+            Self::SetTextMetrics { .. } => "SetTextMetrics",
+            Self::Serial(_) => "Serial",
+            Self::SetSystemTime { .. } => "SetSystemTime",
+            Self::AvailableControllers(_) => "AvailableControllers",
+            Self::SetGpsField(_) => "SetGpsField",
+            Self::VisionFrame { .. } => "VisionFrame",
+            Self::SetEncoderPosition { .. } => "SetEncoderPosition",
+            Self::ConfigureWatchdog(_) => "ConfigureWatchdog",
+            Self::LegalityReport(_) => "LegalityReport",
+            Self::SetSerialPath { .. } => "SetSerialPath",
+            Self::SetScreenshotTriggers { .. } => "SetScreenshotTriggers",
+            Self::SetDeviceLatency { .. } => "SetDeviceLatency",
+            Self::ConfigureDevices(_) => "ConfigureDevices",
+            Self::SelectProgram { .. } => "SelectProgram",
+            Self::RunProgram { .. } => "RunProgram",
+            Self::StopProgram => "StopProgram",
+            Self::SetInputProcessing { .. } => "SetInputProcessing",
+            Self::Sync { .. } => "Sync",
+            Self::FrameStats(_) => "FrameStats",
+            Self::SelectAutonRoutine { .. } => "SelectAutonRoutine",
+            Self::DistanceInput { .. } => "DistanceInput",
+            Self::ApplyDisturbance { .. } => "ApplyDisturbance",
+            Self::SetValueGenerator { .. } => "SetValueGenerator",
+            Self::VisionDetections { .. } => "VisionDetections",
+            Self::InjectFault { .. } => "InjectFault",
+            Self::GpsInput { .. } => "GpsInput",
+            Self::AdvanceAdiEncoder { .. } => "AdvanceAdiEncoder",
+            Self::UltrasonicInput { .. } => "UltrasonicInput",
+            Self::LineTrackerInput { .. } => "LineTrackerInput",
+            Self::AccelerometerInput { .. } => "AccelerometerInput",
+            Self::PortSerial(_) => "PortSerial",
+            Self::SetAnalogFilter { .. } => "SetAnalogFilter",
+            Self::HandshakeRejected(_) => "HandshakeRejected",
+            Self::SetMotorTelemetry { .. } => "SetMotorTelemetry",
+        }
+    }
 
-impl Default for CompetitionMode {
-    fn default() -> Self {
-        Self {
-            enabled: true,
-            connected: false,
-            mode: CompMode::Driver,
-            is_competition: false,
+    /// The combined length of the base64-encoded payloads in this command, in bytes.
+    pub fn payload_len(&self) -> usize {
+        match self {
+            Self::Serial(data) => data.data.len(),
+            Self::VisionFrame { frame, .. } => frame.data.len(),
+            Self::PortSerial(data) => data.data.len(),
+            _ => 0,
         }
     }
 }
 
+/// The name and version of a simulator or frontend implementation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Implementation {
+    pub name: String,
+    pub version: String,
+}
+
 /// Base64-encoded program metadata.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct VCodeSig(pub String);
@@ -171,273 +798,196 @@ impl VCodeSig {
     }
 }
 
-/// The configuration of a V5 peripheral.
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
-#[non_exhaustive]
-pub enum Device {
-    Motor {
-        physical_gearset: MotorGearset,
-        moment_of_inertia: f64,
-    },
-}
-
 /// The current state of the robot as a whole.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct RobotState;
 
-/// An instruction for drawing to the robot LCD screen.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum DrawCommand {
-    Fill {
-        shape: Shape,
-    },
-    Stroke {
-        shape: Shape,
-    },
-    CopyBuffer {
-        top_left: Point2<i32>,
-        bottom_right: Point2<i32>,
-        stride: NonZeroU16,
-        /// Base64 string
-        buffer: String,
-    },
-    Write {
-        text: V5Text,
-        location: TextLocation,
-        opaque: bool,
-        background: Color,
-    },
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-pub enum TextLocation {
-    Coordinates { point: Point2<i32> },
-    Line { line: i32 },
-}
-
-impl Default for TextLocation {
-    fn default() -> Self {
-        Self::Coordinates {
-            point: Point2 { x: 0, y: 0 },
-        }
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-pub enum ScrollLocation {
-    Line {
-        line: i32,
-    },
-    Rectangle {
-        top_left: Point2<i32>,
-        bottom_right: Point2<i32>,
-    },
-}
-
-#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
-pub struct V5Text {
-    pub data: String,
-    pub font_family: V5FontFamily,
-    pub font_size: V5FontSize,
-}
-
+/// The component that produced a log message.
 #[derive(
-    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
 )]
-pub enum V5FontFamily {
+pub enum LogSource {
+    /// The simulator itself.
     #[default]
-    UserMono,
-    TimerMono,
+    #[serde(rename = "Simulator")]
+    Simulator,
+    /// The runtime or SDK that user code is built on.
+    #[serde(rename = "Runtime")]
+    Runtime,
+    /// User code.
+    #[serde(rename = "User")]
+    User,
 }
 
-#[derive(
-    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
-)]
-pub enum V5FontSize {
-    Small,
-    #[default]
-    Normal,
-    Large,
+/// The importance level of a log message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LogLevel {
+    #[serde(rename = "Trace")]
+    Trace,
+    #[serde(rename = "Info")]
+    Info,
+    #[serde(rename = "Warn")]
+    Warn,
+    #[serde(rename = "Error")]
+    Error,
 }
 
-#[derive(
-    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
-)]
-pub struct TextMetrics {
-    pub width: usize,
-    pub height: usize,
+/// A location in the source code of a program.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
 }
 
-/// A shape that can be drawn to the robot LCD screen.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum Shape {
-    Rectangle {
-        top_left: Point2<i32>,
-        bottom_right: Point2<i32>,
-    },
-    Circle {
-        center: Point2<i32>,
-        radius: u16,
-    },
-    Pixel {
-        pos: Point2<i32>,
-    },
-    Line {
-        start: Point2<i32>,
-        end: Point2<i32>,
-    },
+fn one() -> u32 {
+    1
 }
 
-/// The current state of a V5 peripheral.
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+/// Why one side refused the other's handshake, sent in [`Event::HandshakeRejected`] or
+/// [`Command::HandshakeRejected`] so that launchers can tell users how to fix the problem.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[non_exhaustive]
-pub enum DeviceStatus {
-    Motor {
-        velocity: f64,
-        reversed: bool,
-        power_draw: f64,
-        torque_output: f64,
-        flags: i32,
-        position: f64,
-        target_position: f64,
-        voltage: f64,
-        gearset: MotorGearset,
-        brake_mode: MotorBrakeMode,
+pub enum HandshakeRejection {
+    /// The peer's protocol version is not supported.
+    #[serde(rename = "UnsupportedVersion")]
+    UnsupportedVersion {
+        /// The version the peer offered.
+        offered: i32,
+        /// The versions the rejecting side supports, so the peer can tell whether it is too old
+        /// or too new.
+        supported: Vec<i32>,
+    },
+    /// The peer did not advertise extensions the rejecting side requires.
+    #[serde(rename = "MissingExtensions")]
+    MissingExtensions { extensions: Vec<String> },
+    /// Another connection already fills the role the peer is trying to take, such as a second
+    /// frontend connecting to a simulator that only supports one.
+    #[serde(rename = "RoleConflict")]
+    RoleConflict {
+        /// How long to wait before trying again, in milliseconds, or `None` if retrying is not
+        /// expected to help.
+        retry_after_ms: Option<u64>,
     },
 }
 
-/// The gearset of a VEX V5 motor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum MotorGearset {
-    Red,
-    Green,
-    Blue,
-}
-
-/// The brake mode of a VEX V5 motor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum MotorBrakeMode {
-    Coast,
-    Brake,
-    Hold,
-}
-
-/// The mode of a [VEXlink](https://drive.google.com/file/d/13mTA6BT7CPskJzh4YgsfAfoH9OgK75Hn/view)-configured radio.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum LinkMode {
-    Manager,
-    Worker,
+impl fmt::Display for HandshakeRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion { offered, supported } => {
+                write!(f, "protocol version {offered} is not supported")?;
+                match (supported.iter().min(), supported.iter().max()) {
+                    (Some(min), _) if min > offered => {
+                        write!(f, " (protocol version {min} or newer is required)")
+                    }
+                    (_, Some(max)) if max < offered => {
+                        write!(f, " (protocol version {max} or older is required)")
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Self::MissingExtensions { extensions } => {
+                write!(f, "missing required extensions: {}", extensions.join(", "))
+            }
+            Self::RoleConflict { retry_after_ms } => {
+                f.write_str("another connection already has this role")?;
+                match retry_after_ms {
+                    Some(ms) => write!(f, " (try again in {ms} ms)"),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
 }
 
-/// The gearset of a VEX V5 motor.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum TouchEvent {
-    Released,
-    Pressed,
-    Held,
+/// A notification raised by user code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+    /// How urgent the notification is. Frontends may show [`LogLevel::Error`] notifications as
+    /// dialogs and others as toasts.
+    pub severity: LogLevel,
 }
 
-/// An arbitrary port on the VEX V5.
+/// A failure that can be injected into the simulated robot.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum Port {
-    Smart(SmartPort),
-    Adi(AdiPort),
+#[non_exhaustive]
+pub enum Fault {
+    /// The device on `port` stops responding, as if its cable were unplugged.
+    #[serde(rename = "DeviceTimeout")]
+    DeviceTimeout { port: Port },
+    /// The radio link to the controllers drops, so controller input stops updating.
+    #[serde(rename = "RadioDrop")]
+    RadioDrop,
+    /// The battery disconnects, cutting power to motors while the brain stays on.
+    #[serde(rename = "BatteryDisconnect")]
+    BatteryDisconnect,
+    /// Reads from and writes to the SD card fail.
+    #[serde(rename = "SdError")]
+    SdError,
+    /// The screen stops updating, though user code can still draw to it.
+    #[serde(rename = "ScreenFreeze")]
+    ScreenFreeze,
 }
 
-/// An RJ9 4p4c "Smart" port on the VEX V5.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct SmartPort(pub u8);
-
-/// A 3-wire "ADI" port for analog devices.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct AdiPort(pub u8);
-
-/// The current stage of a competition.
+/// Limits on how long the simulator may run user code.
+///
+/// A limit of `None` disables that check.
 #[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
 )]
-pub enum CompMode {
-    Auto,
-    #[default]
-    Driver,
+pub struct WatchdogConfig {
+    /// The maximum wall-clock time a single simulation tick may take, in milliseconds.
+    pub max_tick_ms: Option<u64>,
+    /// The maximum wall-clock time the program may run for, in milliseconds.
+    pub max_run_ms: Option<u64>,
 }
 
-/// The importance level of a log message.
+/// The limit that caused a watchdog to expire.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum LogLevel {
-    Trace,
-    Info,
-    Warn,
-    Error,
-}
-
-/// Battery status and statistics.
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct Battery {
-    pub voltage: f64,
-    pub current: f64,
-    pub capacity: f64,
-}
-
-/// A method of retrieving a controller's current state.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum ControllerUpdate {
-    /// Implementors can send raw controller state to the simulator,
-    /// allowing for keyboard-and-mouse-based control.
-    Raw(ControllerState),
-    /// Implementors can can send the UUID of a physical controller (more efficient and allows for SDL2 mappings).
-    UUID(String),
+pub enum WatchdogTimeout {
+    #[serde(rename = "Tick")]
+    Tick,
+    #[serde(rename = "Run")]
+    Run,
 }
 
-/// The raw state of a VEX V5 controller.
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
-)]
-pub struct ControllerState {
-    pub axis1: i32,
-    pub axis2: i32,
-    pub axis3: i32,
-    pub axis4: i32,
-    pub button_l1: bool,
-    pub button_l2: bool,
-    pub button_r1: bool,
-    pub button_r2: bool,
-    pub button_up: bool,
-    pub button_down: bool,
-    pub button_left: bool,
-    pub button_right: bool,
-    pub button_x: bool,
-    pub button_b: bool,
-    pub button_y: bool,
-    pub button_a: bool,
-    pub button_sel: bool,
-    pub battery_level: i32,
-    pub button_all: bool,
-    pub flags: i32,
-    pub battery_capacity: i32,
+/// Totals describing a completed run of user code.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct RunSummary {
+    /// The amount of simulated time that passed during the run, in milliseconds.
+    pub simulated_ms: u64,
+    /// The number of [`LogLevel::Error`] messages logged.
+    pub error_count: u32,
+    /// The number of [`LogLevel::Warn`] messages logged.
+    pub warning_count: u32,
+    /// The number of times user code panicked.
+    pub panic_count: u32,
+    pub assertions: Vec<AssertionResult>,
+    /// The highest temperature each motor reached.
+    pub max_motor_temperatures: Vec<MotorTemperature>,
 }
 
-#[derive(
-    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, Default,
-)]
-pub struct Color(pub u32);
-
-impl From<RGB8> for Color {
-    fn from(rgb: RGB8) -> Self {
-        Self(u32::from_be_bytes([0, rgb.r, rgb.g, rgb.b]))
+impl RunSummary {
+    /// Whether the run completed without panicking or failing any assertions.
+    pub fn passed(&self) -> bool {
+        self.panic_count == 0 && self.assertions.iter().all(|assertion| assertion.passed)
     }
 }
 
-impl From<Color> for RGB8 {
-    fn from(color: Color) -> Self {
-        let [_, r, g, b] = color.0.to_be_bytes();
-        RGB8 { r, g, b }
-    }
+/// The outcome of a check made during a run.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub name: String,
+    pub passed: bool,
+    pub message: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
-pub struct Rect {
-    pub top_left: Point2<i32>,
-    pub bottom_right: Point2<i32>,
+/// The temperature of a motor, in degrees Celsius.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct MotorTemperature {
+    pub port: SmartPort,
+    pub celsius: f64,
 }
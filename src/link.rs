@@ -0,0 +1,12 @@
+//! VEXlink radio connections between robots.
+
+use serde::{Deserialize, Serialize};
+
+/// The mode of a [VEXlink](https://drive.google.com/file/d/13mTA6BT7CPskJzh4YgsfAfoH9OgK75Hn/view)-configured radio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LinkMode {
+    #[serde(rename = "Manager")]
+    Manager,
+    #[serde(rename = "Worker")]
+    Worker,
+}
@@ -0,0 +1,89 @@
+//! Device inputs generated by the simulator itself, so that long automated runs do not need a
+//! frontend streaming input continuously.
+
+use std::f64::consts::TAU;
+
+use serde::{Deserialize, Serialize};
+
+/// A program that produces a device input value at each point in time.
+///
+/// Times are measured from when the generator was installed.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum ValueGenerator {
+    #[serde(rename = "Constant")]
+    Constant { value: f64 },
+    /// Moves linearly from `start` to `end` over `duration_ms`, then holds at `end`.
+    #[serde(rename = "Ramp")]
+    Ramp {
+        start: f64,
+        end: f64,
+        duration_ms: u64,
+    },
+    /// Oscillates around `offset`.
+    #[serde(rename = "Sine")]
+    Sine {
+        offset: f64,
+        amplitude: f64,
+        period_ms: u64,
+    },
+    /// Steps through a series of sampled values, holding each for `interval_ms`.
+    ///
+    /// After the last sample, the series starts over if `repeat` is set and holds the last
+    /// value otherwise.
+    #[serde(rename = "Samples")]
+    Samples {
+        interval_ms: u64,
+        values: Vec<f64>,
+        repeat: bool,
+    },
+}
+
+impl ValueGenerator {
+    /// The value produced `elapsed_ms` milliseconds after the generator was installed.
+    pub fn value_at(&self, elapsed_ms: u64) -> f64 {
+        match self {
+            Self::Constant { value } => *value,
+            Self::Ramp {
+                start,
+                end,
+                duration_ms,
+            } => {
+                if elapsed_ms >= *duration_ms {
+                    *end
+                } else {
+                    start + (end - start) * elapsed_ms as f64 / *duration_ms as f64
+                }
+            }
+            Self::Sine {
+                offset,
+                amplitude,
+                period_ms,
+            } => {
+                if *period_ms == 0 {
+                    return *offset;
+                }
+                let phase = (elapsed_ms % period_ms) as f64 / *period_ms as f64;
+                offset + amplitude * (phase * TAU).sin()
+            }
+            Self::Samples {
+                interval_ms,
+                values,
+                repeat,
+            } => {
+                let Some(&last) = values.last() else {
+                    return 0.0;
+                };
+                let index = elapsed_ms
+                    .checked_div(*interval_ms)
+                    .unwrap_or(0)
+                    .try_into()
+                    .unwrap_or(usize::MAX);
+                if *repeat {
+                    values[index % values.len()]
+                } else {
+                    values.get(index).copied().unwrap_or(last)
+                }
+            }
+        }
+    }
+}
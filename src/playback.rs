@@ -0,0 +1,165 @@
+//! Recording controller input and replaying it as commands.
+//!
+//! An [`InputTrace`] captures a driver's controller input over a run so it can be replayed
+//! against modified code. Playback can add [`Jitter`] to timing and joystick values so that
+//! code is tested against input that is similar to, but not exactly, the original run.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controller::{ControllerState, ControllerUpdate},
+    recording::{Message, Recording},
+    Command,
+};
+
+/// The state of both controllers at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct InputSample {
+    /// The time of the sample, in milliseconds since the start of the trace.
+    pub time_ms: u64,
+    pub primary: Option<ControllerState>,
+    pub partner: Option<ControllerState>,
+}
+
+/// A timestamped sequence of controller input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct InputTrace {
+    /// The recorded samples, sorted by time.
+    pub samples: Vec<InputSample>,
+}
+
+impl InputTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sample to the end of the trace if the input has changed since the last sample.
+    pub fn record(
+        &mut self,
+        time_ms: u64,
+        primary: Option<ControllerState>,
+        partner: Option<ControllerState>,
+    ) {
+        let changed = self.samples.last().map_or(true, |last| {
+            last.primary != primary || last.partner != partner
+        });
+        if changed {
+            self.samples.push(InputSample {
+                time_ms,
+                primary,
+                partner,
+            });
+        }
+    }
+
+    /// Extracts the raw controller input sent during a recorded session.
+    ///
+    /// Updates that refer to physical controllers by UUID are skipped.
+    pub fn from_recording(recording: &Recording) -> Self {
+        let mut trace = Self::new();
+        for entry in &recording.entries {
+            if let Message::Command(Command::ControllerUpdate(primary, partner)) = &entry.message {
+                let raw = |update: &Option<ControllerUpdate>| match update {
+                    Some(ControllerUpdate::Raw(state)) => Some(*state),
+                    _ => None,
+                };
+                if raw(primary).is_some() || raw(partner).is_some() {
+                    trace.record(entry.time_ms, raw(primary), raw(partner));
+                }
+            }
+        }
+        trace
+    }
+
+    /// Replays the trace as `(time_ms, command)` pairs, applying `jitter` to each sample.
+    ///
+    /// Times remain in non-decreasing order after jitter is applied.
+    pub fn playback(&self, jitter: Jitter) -> Playback<'_> {
+        Playback {
+            samples: self.samples.iter(),
+            jitter,
+            rng: Rng(jitter.seed | 1),
+            last_time: 0,
+        }
+    }
+}
+
+/// Random variation applied to input during playback.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct Jitter {
+    /// The maximum amount each sample is moved earlier or later, in milliseconds.
+    pub timing_ms: u32,
+    /// The maximum amount added to or subtracted from each joystick axis.
+    pub axis_noise: i32,
+    /// The seed for the random number generator, so that playback is reproducible.
+    pub seed: u64,
+}
+
+/// An iterator over the commands that replay an [`InputTrace`].
+#[derive(Debug, Clone)]
+pub struct Playback<'a> {
+    samples: std::slice::Iter<'a, InputSample>,
+    jitter: Jitter,
+    rng: Rng,
+    last_time: u64,
+}
+
+impl Iterator for Playback<'_> {
+    type Item = (u64, Command);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let sample = self.samples.next()?;
+
+        let offset = self.rng.range(i64::from(self.jitter.timing_ms));
+        let time_ms = sample
+            .time_ms
+            .saturating_add_signed(offset)
+            .max(self.last_time);
+        self.last_time = time_ms;
+
+        let mut perturb = |state: Option<ControllerState>| {
+            state.map(|mut state| {
+                // Noise wider than an axis's whole range has the same effect as the full range.
+                let noise = i64::from(self.jitter.axis_noise).min(254);
+                for axis in [
+                    &mut state.axis1,
+                    &mut state.axis2,
+                    &mut state.axis3,
+                    &mut state.axis4,
+                ] {
+                    *axis = axis
+                        .saturating_add(self.rng.range(noise) as i32)
+                        .clamp(-127, 127);
+                }
+                ControllerUpdate::Raw(state)
+            })
+        };
+        let primary = perturb(sample.primary);
+        let partner = perturb(sample.partner);
+
+        Some((time_ms, Command::ControllerUpdate(primary, partner)))
+    }
+}
+
+/// A small xorshift random number generator, so playback does not depend on a random crate.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A uniformly distributed value in `-max..=max`.
+    fn range(&mut self, max: i64) -> i64 {
+        if max <= 0 {
+            return 0;
+        }
+        (self.next() % (2 * max as u64 + 1)) as i64 - max
+    }
+}
@@ -0,0 +1,14 @@
+//! The types and helpers most simulators and frontends need, for glob importing with
+//! `use vexide_simulator_protocol::prelude::*`.
+
+#[cfg(feature = "codec")]
+pub use crate::codec::{
+    negotiate_as_frontend, negotiate_as_simulator, CodecError, HandshakeOptions, Negotiated,
+    Reader, Writer,
+};
+pub use crate::{
+    extensions, AdiPort, Battery, Color, Command, CompMode, CompetitionMode, ControllerId,
+    ControllerState, ControllerUpdate, Device, DeviceStatus, DrawCommand, Event, LogLevel,
+    LogSource, MotorBrakeMode, MotorGearset, Port, Rect, SerialData, Shape, SmartPort, TouchEvent,
+    PROTOCOL_VERSION,
+};
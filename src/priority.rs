@@ -0,0 +1,302 @@
+//! Scheduling outgoing messages by importance, so that urgent messages are not stuck behind
+//! large batches of screen data when the transport is congested.
+//!
+//! Reordering is only safe between messages that don't depend on each other. Messages that
+//! start or end part of a session, such as [`Event::Exited`], [`Event::DisplayClosed`], and
+//! [`Command::ConfigureDevice`], and resynchronization markers, which must stay at a known
+//! point in the stream, are *barriers*: [`SendQueue`] sends everything queued before a
+//! barrier first, and nothing queued after it is sent ahead of it. Other messages may be sent
+//! ahead of less urgent messages queued before them.
+
+use std::collections::VecDeque;
+
+use crate::{Command, Event};
+
+/// How urgently a message should be delivered, from least to most urgent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Priority {
+    /// Log messages and serial output.
+    Log,
+    /// Screen drawing and screenshots.
+    Screen,
+    /// Device statuses and other sensor data.
+    Device,
+    /// User input, such as touches and controller state.
+    Input,
+    /// Messages that control the session, such as the handshake and competition mode changes.
+    Control,
+}
+
+impl Priority {
+    const ALL: [Self; 5] = [
+        Self::Control,
+        Self::Input,
+        Self::Device,
+        Self::Screen,
+        Self::Log,
+    ];
+}
+
+impl Event {
+    /// The priority this event should be sent with.
+    pub fn priority(&self) -> Priority {
+        match self {
+            Self::ScreenDraw { .. }
+            | Self::ScreenScroll { .. }
+            | Self::ScreenClear { .. }
+            | Self::ScreenDoubleBufferMode { .. }
+            | Self::ScreenRender
+            | Self::DisplayDraw { .. }
+            | Self::DisplayClear { .. }
+            | Self::DisplayRender { .. }
+            | Self::Screenshot { .. } => Priority::Screen,
+            Self::DeviceUpdate { .. }
+            | Self::DeviceDelta { .. }
+            | Self::DeviceLed { .. }
+            | Self::Battery(_)
+            | Self::RobotPose { .. }
+            | Self::RobotState(_)
+            | Self::AdiOutput { .. }
+            | Self::AdiLedBuffer { .. }
+            | Self::AdiPwmOutput { .. }
+            | Self::AdiAnalogOutput { .. }
+            | Self::MotorTelemetry { .. } => Priority::Device,
+            Self::PortSerial(_) => Priority::Device,
+            Self::Log { .. } | Self::Serial(_) => Priority::Log,
+            Self::Handshake { .. }
+            | Self::HandshakeRejected(_)
+            | Self::VCodeSig(_)
+            | Self::Ready
+            | Self::Exited
+            | Self::RunSummary(_)
+            | Self::WatchdogExpired { .. }
+            | Self::VEXLinkConnect { .. }
+            | Self::VEXLinkDisconnect { .. }
+            | Self::TextMetricsRequest { .. }
+            | Self::SelectController { .. }
+            | Self::GpsMount { .. }
+            | Self::VisionStream { .. }
+            | Self::LegalityReport(_)
+            | Self::DevicesConfigured(_)
+            | Self::ProgramSlots(_)
+            | Self::Fidelity(_)
+            | Self::Sync { .. }
+            | Self::Notification(_)
+            | Self::AutonRoutines(_)
+            | Self::TouchListening(_)
+            | Self::PortClaimed { .. }
+            | Self::MotorLimits { .. }
+            | Self::DisplayOpened(_)
+            | Self::DisplayClosed { .. } => Priority::Control,
+        }
+    }
+
+    /// Whether this event must not be reordered with any other event. See the
+    /// [module documentation](self).
+    pub fn is_barrier(&self) -> bool {
+        matches!(
+            self,
+            Self::Handshake { .. }
+                | Self::HandshakeRejected(_)
+                | Self::Ready
+                | Self::Exited
+                | Self::RunSummary(_)
+                | Self::DevicesConfigured(_)
+                | Self::DisplayOpened(_)
+                | Self::DisplayClosed { .. }
+                | Self::Sync { .. }
+        )
+    }
+}
+
+impl Command {
+    /// The priority this command should be sent with.
+    pub fn priority(&self) -> Priority {
+        match self {
+            Self::Touch { .. }
+            | Self::ControllerUpdate(..)
+            | Self::AdiInput { .. }
+            | Self::AdvanceAdiEncoder { .. }
+            | Self::UltrasonicInput { .. }
+            | Self::LineTrackerInput { .. }
+            | Self::AccelerometerInput { .. }
+            | Self::DistanceInput { .. }
+            | Self::GpsInput { .. }
+            | Self::Serial(_)
+            | Self::PortSerial(_) => Priority::Input,
+            Self::VisionFrame { .. } | Self::VisionDetections { .. } => Priority::Device,
+            Self::Handshake { .. }
+            | Self::HandshakeRejected(_)
+            | Self::StartExecution
+            | Self::SelectProgram { .. }
+            | Self::RunProgram { .. }
+            | Self::StopProgram
+            | Self::CompetitionMode(_)
+            | Self::CompetitionSchedule(_)
+            | Self::ConfigureDevice { .. }
+            | Self::ConfigureDevices(_)
+            | Self::USD { .. }
+            | Self::VEXLinkOpened { .. }
+            | Self::VEXLinkClosed { .. }
+            | Self::SetBatteryCapacity { .. }
+            | Self::SetTextMetrics { .. }
+            | Self::SetSystemTime { .. }
+            | Self::AvailableControllers(_)
+            | Self::SetGpsField(_)
+            | Self::SetEncoderPosition { .. }
+            | Self::ConfigureWatchdog(_)
+            | Self::LegalityReport(_)
+            | Self::SetSerialPath { .. }
+            | Self::SetScreenshotTriggers { .. }
+            | Self::SetDeviceLatency { .. }
+            | Self::Sync { .. }
+            | Self::SetInputProcessing { .. }
+            | Self::FrameStats(_)
+            | Self::SelectAutonRoutine { .. }
+            | Self::ApplyDisturbance { .. }
+            | Self::SetValueGenerator { .. }
+            | Self::InjectFault { .. }
+            | Self::SetAnalogFilter { .. }
+            | Self::SetMotorTelemetry { .. } => Priority::Control,
+        }
+    }
+
+    /// Whether this command must not be reordered with any other command. See the
+    /// [module documentation](self).
+    pub fn is_barrier(&self) -> bool {
+        matches!(
+            self,
+            Self::Handshake { .. }
+                | Self::HandshakeRejected(_)
+                | Self::StartExecution
+                | Self::RunProgram { .. }
+                | Self::StopProgram
+                | Self::CompetitionMode(_)
+                | Self::CompetitionSchedule(_)
+                | Self::ConfigureDevice { .. }
+                | Self::ConfigureDevices(_)
+                | Self::SetSerialPath { .. }
+                | Self::Sync { .. }
+        )
+    }
+}
+
+/// A queue of outgoing messages that yields the most urgent message first.
+///
+/// Messages with the same priority are yielded in the order they were pushed, so the relative
+/// order of, for example, screen events is preserved. Barriers pushed with
+/// [`SendQueue::push_barrier`] are never reordered with other messages.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SendQueue<T> {
+    /// Runs of messages separated by barriers. Every segment but the last ends with a barrier.
+    segments: VecDeque<Segment<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Segment<T> {
+    queues: [VecDeque<T>; 5],
+    barrier: Option<(Priority, T)>,
+}
+
+impl<T> Segment<T> {
+    fn new() -> Self {
+        Self {
+            queues: Default::default(),
+            barrier: None,
+        }
+    }
+
+    fn len_of(&self, priority: Priority) -> usize {
+        let barrier = self
+            .barrier
+            .as_ref()
+            .is_some_and(|(barrier, _)| *barrier == priority);
+        self.queues[priority as usize].len() + usize::from(barrier)
+    }
+}
+
+impl<T> SendQueue<T> {
+    pub fn new() -> Self {
+        Self {
+            segments: VecDeque::from([Segment::new()]),
+        }
+    }
+
+    fn last(&mut self) -> &mut Segment<T> {
+        self.segments.back_mut().expect("queue has no open segment")
+    }
+
+    /// Adds a message to the back of the queue for its priority.
+    pub fn push(&mut self, priority: Priority, message: T) {
+        self.last().queues[priority as usize].push_back(message);
+    }
+
+    /// Adds a message that is sent only after every message already queued, and before every
+    /// message queued after it.
+    pub fn push_barrier(&mut self, priority: Priority, message: T) {
+        self.last().barrier = Some((priority, message));
+        self.segments.push_back(Segment::new());
+    }
+
+    /// Removes the oldest message with the highest priority that is not held back by a
+    /// barrier.
+    pub fn pop(&mut self) -> Option<T> {
+        let first = self.segments.front_mut()?;
+        if let Some(message) = Priority::ALL
+            .iter()
+            .find_map(|&priority| first.queues[priority as usize].pop_front())
+        {
+            return Some(message);
+        }
+        let (_, barrier) = first.barrier.take()?;
+        self.segments.pop_front();
+        Some(barrier)
+    }
+
+    /// The number of queued messages with the given priority.
+    pub fn len_of(&self, priority: Priority) -> usize {
+        self.segments
+            .iter()
+            .map(|segment| segment.len_of(priority))
+            .sum()
+    }
+
+    pub fn len(&self) -> usize {
+        Priority::ALL
+            .iter()
+            .map(|&priority| self.len_of(priority))
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Default for SendQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SendQueue<Event> {
+    /// Queues an event with its own priority, as a barrier if it is one.
+    pub fn push_event(&mut self, event: Event) {
+        if event.is_barrier() {
+            self.push_barrier(event.priority(), event);
+        } else {
+            self.push(event.priority(), event);
+        }
+    }
+}
+
+impl SendQueue<Command> {
+    /// Queues a command with its own priority, as a barrier if it is one.
+    pub fn push_command(&mut self, command: Command) {
+        if command.is_barrier() {
+            self.push_barrier(command.priority(), command);
+        } else {
+            self.push(command.priority(), command);
+        }
+    }
+}
@@ -0,0 +1,107 @@
+//! Forwarding messages between a simulator and a frontend while inspecting or rewriting them.
+//!
+//! A connection has two independent directions, so a man-in-the-middle tool uses two
+//! [`Proxy`]s, typically running on separate threads: one created with [`Proxy::events`] that
+//! forwards the simulator's events to the frontend, and one created with [`Proxy::commands`]
+//! that forwards the frontend's commands to the simulator.
+
+use std::{
+    fmt,
+    io::{BufRead, Write},
+};
+
+use crate::{
+    codec::{CodecError, Reader, Writer},
+    Command, Event,
+};
+
+type Filter<T> = Box<dyn FnMut(T) -> Option<T> + Send>;
+
+/// Forwards messages in one direction of a connection, passing each through a chain of filters.
+pub struct Proxy<R, W, T> {
+    reader: Reader<R>,
+    writer: Writer<W>,
+    read: fn(&mut Reader<R>) -> Result<Option<T>, CodecError>,
+    write: fn(&mut Writer<W>, &T) -> Result<(), CodecError>,
+    filters: Vec<Filter<T>>,
+}
+
+impl<R: BufRead, W: Write> Proxy<R, W, Event> {
+    /// Creates a proxy that forwards events from a simulator to a frontend.
+    pub fn events(reader: Reader<R>, writer: Writer<W>) -> Self {
+        Self::new(reader, writer, Reader::read_event, Writer::write_event)
+    }
+}
+
+impl<R: BufRead, W: Write> Proxy<R, W, Command> {
+    /// Creates a proxy that forwards commands from a frontend to a simulator.
+    pub fn commands(reader: Reader<R>, writer: Writer<W>) -> Self {
+        Self::new(reader, writer, Reader::read_command, Writer::write_command)
+    }
+}
+
+impl<R: BufRead, W: Write, T> Proxy<R, W, T> {
+    fn new(
+        reader: Reader<R>,
+        writer: Writer<W>,
+        read: fn(&mut Reader<R>) -> Result<Option<T>, CodecError>,
+        write: fn(&mut Writer<W>, &T) -> Result<(), CodecError>,
+    ) -> Self {
+        Self {
+            reader,
+            writer,
+            read,
+            write,
+            filters: Vec::new(),
+        }
+    }
+
+    /// Adds a filter that runs after any filters added before it.
+    ///
+    /// A filter may return the message unchanged, return a different message in its place, or
+    /// return `None` to drop it. Filters can also hold state, such as to rate-limit a kind of
+    /// message.
+    pub fn with_filter(mut self, filter: impl FnMut(T) -> Option<T> + Send + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Forwards the next message, returning `false` once the stream has ended.
+    ///
+    /// The message is sent as soon as it passes every filter, encoded for the writer's
+    /// [`version`](Writer::version), and the writer is flushed.
+    pub fn forward(&mut self) -> Result<bool, CodecError> {
+        let Some(message) = (self.read)(&mut self.reader)? else {
+            return Ok(false);
+        };
+        let mut message = Some(message);
+        for filter in &mut self.filters {
+            message = message.and_then(&mut *filter);
+        }
+        if let Some(message) = message {
+            (self.write)(&mut self.writer, &message)?;
+            self.writer.flush()?;
+        }
+        Ok(true)
+    }
+
+    /// Forwards messages until the stream ends.
+    pub fn run(&mut self) -> Result<(), CodecError> {
+        while self.forward()? {}
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> (Reader<R>, Writer<W>) {
+        (self.reader, self.writer)
+    }
+}
+
+impl<R: fmt::Debug, W: fmt::Debug, T> fmt::Debug for Proxy<R, W, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Proxy")
+            .field("reader", &self.reader)
+            .field("writer", &self.writer)
+            .field("filters", &self.filters.len())
+            .finish()
+    }
+}
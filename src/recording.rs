@@ -0,0 +1,342 @@
+//! Recorded simulator sessions.
+//!
+//! A recording file is newline-delimited JSON: a [`RecordingHeader`] on the first line followed
+//! by one [`Entry`] per line, in the order the messages were sent.
+
+#[cfg(feature = "codec")]
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "codec")]
+use crate::codec::{CodecError, Reader, Writer};
+use crate::{
+    geometry::Rect,
+    screen::{SCREEN_HEIGHT, SCREEN_WIDTH},
+    session::{Implementations, SessionInfo},
+    state::RobotStateTracker,
+    Command, Event, Implementation, VCodeSig,
+};
+
+/// Metadata written at the start of a session recording.
+///
+/// Replay tools can use the header to filter and label recordings without
+/// reading the rest of the message stream.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    protocol_version: i32,
+    start_time: u64,
+    program: Option<VCodeSig>,
+    simulator: Option<Implementation>,
+    frontend: Option<Implementation>,
+    field: Option<String>,
+    #[serde(default)]
+    extensions: Vec<String>,
+}
+
+impl RecordingHeader {
+    /// Creates a header for a recording that started at `start_time`, in milliseconds since the Unix epoch.
+    pub fn new(protocol_version: i32, start_time: u64) -> Self {
+        Self {
+            protocol_version,
+            start_time,
+            program: None,
+            simulator: None,
+            frontend: None,
+            field: None,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Creates a header for a recording of the described session.
+    pub fn from_session(session: &SessionInfo) -> Self {
+        Self {
+            simulator: session.implementations.simulator.clone(),
+            frontend: session.implementations.frontend.clone(),
+            extensions: session.capabilities.clone(),
+            ..Self::new(session.version, session.start_time)
+        }
+    }
+
+    /// Sets the signature of the program that was run during the session.
+    pub fn with_program(mut self, program: VCodeSig) -> Self {
+        self.program = Some(program);
+        self
+    }
+
+    /// Sets the simulator that produced the session's events.
+    pub fn with_simulator(mut self, simulator: Implementation) -> Self {
+        self.simulator = Some(simulator);
+        self
+    }
+
+    /// Sets the frontend that produced the session's commands.
+    pub fn with_frontend(mut self, frontend: Implementation) -> Self {
+        self.frontend = Some(frontend);
+        self
+    }
+
+    /// Sets the identifier of the field or game season being simulated.
+    pub fn with_field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// The protocol version negotiated during the session's handshake.
+    pub fn protocol_version(&self) -> i32 {
+        self.protocol_version
+    }
+
+    /// The time the recording started, in milliseconds since the Unix epoch.
+    pub fn start_time(&self) -> u64 {
+        self.start_time
+    }
+
+    /// The signature of the program that was run during the session.
+    pub fn program(&self) -> Option<&VCodeSig> {
+        self.program.as_ref()
+    }
+
+    /// The simulator that produced the session's events.
+    pub fn simulator(&self) -> Option<&Implementation> {
+        self.simulator.as_ref()
+    }
+
+    /// The frontend that produced the session's commands.
+    pub fn frontend(&self) -> Option<&Implementation> {
+        self.frontend.as_ref()
+    }
+
+    /// The identifier of the field or game season being simulated.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// The extensions negotiated during the session's handshake.
+    ///
+    /// Recordings made before extensions were recorded have none.
+    pub fn extensions(&self) -> &[String] {
+        &self.extensions
+    }
+
+    /// Describes the recorded session.
+    pub fn session_info(&self) -> SessionInfo {
+        SessionInfo {
+            version: self.protocol_version,
+            capabilities: self.extensions.clone(),
+            implementations: Implementations {
+                simulator: self.simulator.clone(),
+                frontend: self.frontend.clone(),
+            },
+            start_time: self.start_time,
+        }
+    }
+}
+
+/// A message sent by either side of a session.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum Message {
+    #[serde(rename = "Event")]
+    Event(Event),
+    #[serde(rename = "Command")]
+    Command(Command),
+}
+
+/// A message captured during a session, with the time it was sent.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Entry {
+    /// The time the message was sent, in milliseconds since the start of the recording.
+    pub time_ms: u64,
+    pub message: Message,
+}
+
+/// A complete session recording.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Recording {
+    pub header: RecordingHeader,
+    /// The recorded messages, sorted by time.
+    pub entries: Vec<Entry>,
+}
+
+impl Recording {
+    pub fn new(header: RecordingHeader) -> Self {
+        Self {
+            header,
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of entries sent at or before `time_ms`.
+    pub fn position(&self, time_ms: u64) -> usize {
+        self.entries
+            .partition_point(|entry| entry.time_ms <= time_ms)
+    }
+
+    /// Reconstructs the state of the session at `time_ms` by replaying every earlier entry.
+    pub fn seek_to(&self, time_ms: u64) -> ReplayState {
+        let mut state = ReplayState::default();
+        let mut now = 0;
+        for entry in &self.entries[..self.position(time_ms)] {
+            state.robot.advance(entry.time_ms.saturating_sub(now));
+            now = now.max(entry.time_ms);
+            state.apply(&entry.message);
+        }
+        state.robot.advance(time_ms.saturating_sub(now));
+        state
+    }
+
+    /// Reads a recording file.
+    #[cfg(feature = "codec")]
+    pub fn read_from<R: BufRead>(reader: R) -> Result<Self, CodecError> {
+        let mut reader = Reader::new(reader);
+        let header = reader
+            .read()?
+            .ok_or_else(|| CodecError::Io(std::io::ErrorKind::UnexpectedEof.into()))?;
+        let mut entries = Vec::new();
+        while let Some(entry) = reader.read()? {
+            entries.push(entry);
+        }
+        Ok(Self { header, entries })
+    }
+
+    /// Writes this recording in the recording file format.
+    #[cfg(feature = "codec")]
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), CodecError> {
+        let mut writer = Writer::new(writer);
+        writer.write(&self.header)?;
+        for entry in &self.entries {
+            writer.write(entry)?;
+        }
+        writer.flush()
+    }
+}
+
+/// The byte offset of each entry in a recording file, allowing a replay tool to seek within
+/// a recording without loading all of it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct RecordingIndex {
+    entries: Vec<IndexEntry>,
+}
+
+/// The location of one entry in a recording file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub time_ms: u64,
+    /// The byte offset of the start of the entry's line.
+    pub offset: u64,
+}
+
+impl RecordingIndex {
+    /// Builds an index by scanning a recording file.
+    #[cfg(feature = "codec")]
+    pub fn build<R: BufRead>(mut reader: R) -> Result<Self, CodecError> {
+        #[derive(Deserialize)]
+        struct Timestamp {
+            time_ms: u64,
+        }
+
+        let mut line = String::new();
+        let mut offset = reader.read_line(&mut line)? as u64;
+        let mut entries = Vec::new();
+        loop {
+            line.clear();
+            let len = reader.read_line(&mut line)?;
+            if len == 0 {
+                break;
+            }
+            if !line.trim().is_empty() {
+                let Timestamp { time_ms } = crate::codec::decode(&line)?;
+                entries.push(IndexEntry { time_ms, offset });
+            }
+            offset += len as u64;
+        }
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// The byte offset of the first entry sent after `time_ms`, or `None` if there is none.
+    ///
+    /// Reading a file up to this offset replays everything sent at or before `time_ms`.
+    pub fn offset_after(&self, time_ms: u64) -> Option<u64> {
+        let position = self
+            .entries
+            .partition_point(|entry| entry.time_ms <= time_ms);
+        self.entries.get(position).map(|entry| entry.offset)
+    }
+}
+
+/// The state of a session reconstructed from recorded messages.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct ReplayState {
+    pub robot: RobotStateTracker,
+    /// The screen events needed to redraw the screen, starting from the most recent clear of
+    /// the entire visible screen and preceded by the latest
+    /// [`ScreenDoubleBufferMode`](Event::ScreenDoubleBufferMode) event, if any.
+    pub screen: Vec<Event>,
+    double_buffered: bool,
+    /// The position in `screen` of a clear of the entire back buffer that has not been
+    /// rendered yet.
+    pending_clear: Option<usize>,
+}
+
+impl ReplayState {
+    /// Updates the state to reflect a message.
+    pub fn apply(&mut self, message: &Message) {
+        match message {
+            Message::Event(event) => {
+                self.robot.apply_event(event);
+                match event {
+                    Event::ScreenClear { clip_region, .. } if covers_screen(clip_region) => {
+                        if self.double_buffered {
+                            // The clear only reaches the back buffer, so the visible frame
+                            // still depends on earlier events until it is rendered.
+                            self.pending_clear = Some(self.screen.len());
+                        } else {
+                            self.truncate_screen(self.screen.len());
+                        }
+                        self.screen.push(event.clone());
+                    }
+                    Event::ScreenRender => {
+                        if let Some(clear) = self.pending_clear.take() {
+                            self.truncate_screen(clear);
+                        }
+                        self.screen.push(event.clone());
+                    }
+                    Event::ScreenDoubleBufferMode { enable } => {
+                        self.double_buffered = *enable;
+                        self.pending_clear = None;
+                        self.screen.push(event.clone());
+                    }
+                    Event::ScreenDraw { .. }
+                    | Event::ScreenScroll { .. }
+                    | Event::ScreenClear { .. } => self.screen.push(event.clone()),
+                    _ => {}
+                }
+            }
+            Message::Command(command) => self.robot.apply_command(command),
+        }
+    }
+
+    /// Discards the screen events before `start`, keeping the buffering mode they set.
+    fn truncate_screen(&mut self, start: usize) {
+        let mode = self.screen[..start]
+            .iter()
+            .rev()
+            .find(|event| matches!(event, Event::ScreenDoubleBufferMode { .. }))
+            .cloned();
+        self.screen.drain(..start);
+        if let Some(mode) = mode {
+            self.screen.insert(0, mode);
+        }
+    }
+}
+
+fn covers_screen(rect: &Rect) -> bool {
+    rect.top_left.x <= 0
+        && rect.top_left.y <= 0
+        && rect.bottom_right.x >= SCREEN_WIDTH as i32 - 1
+        && rect.bottom_right.y >= SCREEN_HEIGHT as i32 - 1
+}
@@ -0,0 +1,161 @@
+//! Portable scripts for automated simulator runs.
+//!
+//! A [`Scenario`] is a list of steps that send commands to a simulator and wait for events
+//! that satisfy assertions, such as "the robot ends up within 5 cm of (1, 2)". Scenarios are
+//! serializable so the same script can be run against any simulator.
+
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    thread,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::codec::{CodecError, ReadTimeout, Reader, Writer};
+use crate::{Command, Event, Fidelity};
+
+/// A script for an automated simulator run.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub steps: Vec<Step>,
+}
+
+/// A single action in a [`Scenario`].
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum Step {
+    /// Sends a command to the simulator.
+    #[serde(rename = "Send")]
+    Send(Command),
+    /// Pauses for the given number of milliseconds.
+    #[serde(rename = "Wait")]
+    Wait { ms: u64 },
+    /// Waits until the simulator sends an event satisfying `assertion`, failing if none arrives
+    /// within `timeout_ms` milliseconds.
+    #[serde(rename = "Expect")]
+    Expect {
+        assertion: Assertion,
+        timeout_ms: u64,
+    },
+}
+
+/// A condition that an event must satisfy.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum Assertion {
+    /// Any event of the given kind, as returned by [`Event::kind`].
+    #[serde(rename = "Kind")]
+    Kind { kind: String },
+    /// A log message containing the given text.
+    #[serde(rename = "Log")]
+    Log { contains: String },
+    /// A robot pose within `tolerance` meters of the given position.
+    #[serde(rename = "Pose")]
+    Pose { x: f64, y: f64, tolerance: f64 },
+    /// The program exited.
+    #[serde(rename = "Exited")]
+    Exited,
+    /// A fidelity declaration at least as faithful as `minimum`.
+    #[serde(rename = "Fidelity")]
+    Fidelity { minimum: Fidelity },
+}
+
+impl Assertion {
+    /// Whether `event` satisfies this assertion.
+    pub fn matches(&self, event: &Event) -> bool {
+        match (self, event) {
+            (Self::Kind { kind }, event) => event.kind() == kind,
+            (Self::Log { contains }, Event::Log { message, .. }) => message.contains(contains),
+            (Self::Pose { x, y, tolerance }, Event::RobotPose { x: px, y: py }) => {
+                (px - x).hypot(py - y) <= *tolerance
+            }
+            (Self::Exited, Event::Exited) => true,
+            (Self::Fidelity { minimum }, Event::Fidelity(fidelity)) => fidelity.satisfies(minimum),
+            _ => false,
+        }
+    }
+}
+
+/// An error that caused a scenario to fail.
+#[derive(Debug)]
+pub enum ScenarioError {
+    /// Communication with the simulator failed.
+    Codec(CodecError),
+    /// No event satisfied the assertion of the step at index `step` before its timeout.
+    Timeout { step: usize },
+    /// The simulator closed the connection before the step at index `step` completed.
+    Disconnected { step: usize },
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Codec(err) => write!(f, "{err}"),
+            Self::Timeout { step } => write!(f, "step {step} timed out"),
+            Self::Disconnected { step } => {
+                write!(f, "simulator disconnected during step {step}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<CodecError> for ScenarioError {
+    fn from(err: CodecError) -> Self {
+        Self::Codec(err)
+    }
+}
+
+impl Scenario {
+    /// Runs this scenario against a simulator, returning every event received.
+    ///
+    /// Each [`Step::Expect`] fails as soon as its timeout passes, even if the simulator has
+    /// stopped sending events.
+    pub fn run<R: BufRead + ReadTimeout, W: Write>(
+        &self,
+        reader: &mut Reader<R>,
+        writer: &mut Writer<W>,
+    ) -> Result<Vec<Event>, ScenarioError> {
+        let mut events = Vec::new();
+        for (index, step) in self.steps.iter().enumerate() {
+            match step {
+                Step::Send(command) => {
+                    writer.write_command(command)?;
+                    writer.flush()?;
+                }
+                Step::Wait { ms } => thread::sleep(Duration::from_millis(*ms)),
+                Step::Expect {
+                    assertion,
+                    timeout_ms,
+                } => {
+                    // A timeout too long to represent never passes.
+                    let deadline = Instant::now().checked_add(Duration::from_millis(*timeout_ms));
+                    loop {
+                        let event = match deadline {
+                            Some(deadline) => reader.read_event_until(deadline),
+                            None => reader.read_event(),
+                        };
+                        let event = match event {
+                            Ok(Some(event)) => event,
+                            Ok(None) => return Err(ScenarioError::Disconnected { step: index }),
+                            Err(CodecError::Io(err)) if err.kind() == io::ErrorKind::TimedOut => {
+                                return Err(ScenarioError::Timeout { step: index });
+                            }
+                            Err(err) => return Err(err.into()),
+                        };
+                        let matched = assertion.matches(&event);
+                        events.push(event);
+                        if matched {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(events)
+    }
+}
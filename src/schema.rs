@@ -0,0 +1,182 @@
+//! Runtime descriptions of the messages supported by this version of the crate, so that generic
+//! tools such as viewers and fuzzers can adapt to whatever version they are built against.
+//!
+//! The descriptions are read from the messages' [`Deserialize`] implementations, so they always
+//! match the wire format.
+
+use std::{cell::RefCell, fmt};
+
+use serde::{
+    de::{
+        self, value::StrDeserializer, DeserializeSeed, EnumAccess, IntoDeserializer, VariantAccess,
+        Visitor,
+    },
+    forward_to_deserialize_any, Deserialize, Deserializer, Serialize,
+};
+
+use crate::{Command, Event, PROTOCOL_VERSION};
+
+/// The messages supported by a protocol version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct ProtocolDescriptor {
+    pub version: i32,
+    pub events: Vec<VariantDescriptor>,
+    pub commands: Vec<VariantDescriptor>,
+}
+
+/// A single message variant, as it appears on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct VariantDescriptor {
+    pub name: &'static str,
+    pub shape: VariantShape,
+}
+
+/// The form of a variant's payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum VariantShape {
+    /// No payload, serialized as a bare string.
+    Unit,
+    /// A single unnamed value.
+    Newtype,
+    /// An array of the given number of values.
+    Tuple(usize),
+    /// An object with the given fields.
+    Struct(&'static [&'static str]),
+}
+
+/// Describes every event and command supported by this crate.
+pub fn describe() -> ProtocolDescriptor {
+    ProtocolDescriptor {
+        version: PROTOCOL_VERSION,
+        events: Event::describe(),
+        commands: Command::describe(),
+    }
+}
+
+impl Event {
+    /// Describes every variant of this type.
+    pub fn describe() -> Vec<VariantDescriptor> {
+        describe_enum::<Self>()
+    }
+}
+
+impl Command {
+    /// Describes every variant of this type.
+    pub fn describe() -> Vec<VariantDescriptor> {
+        describe_enum::<Self>()
+    }
+}
+
+fn describe_enum<T: Deserialize<'static>>() -> Vec<VariantDescriptor> {
+    let probe = Probe::default();
+    let _ = T::deserialize(&probe);
+    let variants = probe.variants.take();
+    variants
+        .iter()
+        .map(|&name| {
+            let probe = Probe {
+                variant: Some(name),
+                ..Probe::default()
+            };
+            let _ = T::deserialize(&probe);
+            VariantDescriptor {
+                name,
+                shape: probe.shape.take().unwrap_or(VariantShape::Unit),
+            }
+        })
+        .collect()
+}
+
+/// A deserializer that records the structure of an enum instead of producing a value.
+///
+/// Without a `variant`, it records the enum's variant names. With one, it selects that variant
+/// and records its shape.
+#[derive(Default)]
+struct Probe {
+    variant: Option<&'static str>,
+    variants: RefCell<&'static [&'static str]>,
+    shape: RefCell<Option<VariantShape>>,
+}
+
+/// The error used to stop deserialization once the probe has what it needs.
+#[derive(Debug)]
+struct Stop;
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("schema probe finished")
+    }
+}
+
+impl std::error::Error for Stop {}
+
+impl de::Error for Stop {
+    fn custom<T: fmt::Display>(_msg: T) -> Self {
+        Stop
+    }
+}
+
+impl<'de> Deserializer<'de> for &Probe {
+    type Error = Stop;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Stop> {
+        Err(Stop)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Stop> {
+        self.variants.replace(variants);
+        match self.variant {
+            Some(_) => visitor.visit_enum(self),
+            None => Err(Stop),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct identifier
+        ignored_any
+    }
+}
+
+impl<'de> EnumAccess<'de> for &Probe {
+    type Error = Stop;
+    type Variant = Self;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self), Stop> {
+        let name: StrDeserializer<'_, Stop> = self.variant.unwrap_or_default().into_deserializer();
+        Ok((seed.deserialize(name)?, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for &Probe {
+    type Error = Stop;
+
+    fn unit_variant(self) -> Result<(), Stop> {
+        self.shape.replace(Some(VariantShape::Unit));
+        Err(Stop)
+    }
+
+    fn newtype_variant_seed<S: DeserializeSeed<'de>>(self, _seed: S) -> Result<S::Value, Stop> {
+        self.shape.replace(Some(VariantShape::Newtype));
+        Err(Stop)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, _visitor: V) -> Result<V::Value, Stop> {
+        self.shape.replace(Some(VariantShape::Tuple(len)));
+        Err(Stop)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Stop> {
+        self.shape.replace(Some(VariantShape::Struct(fields)));
+        Err(Stop)
+    }
+}
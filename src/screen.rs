@@ -0,0 +1,666 @@
+//! Drawing to and interacting with the robot LCD screen.
+
+use crate::geometry::Rect;
+use base64::{prelude::*, DecodeError};
+use mint::Point2;
+use rgb::RGB8;
+use serde::{
+    de::{self, Unexpected, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
+use std::{borrow::Cow, fmt, num::NonZeroU16, str::FromStr};
+
+#[cfg(feature = "framebuffer")]
+mod framebuffer;
+
+#[cfg(feature = "framebuffer")]
+pub use framebuffer::Framebuffer;
+
+/// The width of the robot LCD screen, in pixels.
+pub const SCREEN_WIDTH: u32 = 480;
+
+/// The height of the robot LCD screen, in pixels.
+pub const SCREEN_HEIGHT: u32 = 272;
+
+/// The height of a line of text, in pixels, as used by line-based screen locations.
+pub const LINE_HEIGHT: i32 = 20;
+
+/// An instruction for drawing to the robot LCD screen.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(from = "DrawCommandRepr")]
+pub enum DrawCommand {
+    #[serde(rename = "Fill")]
+    Fill { shape: Shape },
+    #[serde(rename = "Stroke")]
+    Stroke { shape: Shape },
+    #[serde(rename = "CopyBuffer")]
+    CopyBuffer {
+        top_left: Point2<i32>,
+        bottom_right: Point2<i32>,
+        stride: NonZeroU16,
+        /// Base64 string
+        buffer: String,
+    },
+    #[serde(rename = "Write")]
+    Write {
+        text: V5Text,
+        location: TextLocation,
+        background: TextBackground,
+    },
+}
+
+/// What is drawn behind text written to the screen.
+///
+/// Version 1 of the protocol describes the background with an `opaque` flag alongside a
+/// `background` color, as in [`v1::DrawCommand`](crate::v1::DrawCommand), and peers that
+/// negotiated version 1 are still sent that form. Both forms are accepted when deserializing a
+/// [`DrawCommand::Write`]:
+///
+/// | Wire fields                              | Background               |
+/// |------------------------------------------|--------------------------|
+/// | `"background": "Transparent"`            | `Transparent`            |
+/// | `"background": {"Solid": 255}`           | `Solid(Color(255))`      |
+/// | `"opaque": false, "background": 255`     | `Transparent`            |
+/// | `"opaque": true, "background": 255`      | `Solid(Color(255))`      |
+/// | `"background": 255` (no `opaque` field)  | `Solid(Color(255))`      |
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum TextBackground {
+    /// Only the glyphs are drawn, leaving the pixels behind them unchanged.
+    #[default]
+    #[serde(rename = "Transparent")]
+    Transparent,
+    /// The text's bounding box is filled with the given color before the glyphs are drawn.
+    #[serde(rename = "Solid")]
+    Solid(Color),
+}
+
+/// The wire format of [`DrawCommand`], accepting the legacy form of [`DrawCommand::Write`].
+#[derive(Deserialize)]
+enum DrawCommandRepr {
+    #[serde(rename = "Fill")]
+    Fill { shape: Shape },
+    #[serde(rename = "Stroke")]
+    Stroke { shape: Shape },
+    #[serde(rename = "CopyBuffer")]
+    CopyBuffer {
+        top_left: Point2<i32>,
+        bottom_right: Point2<i32>,
+        stride: NonZeroU16,
+        buffer: String,
+    },
+    #[serde(rename = "Write")]
+    Write {
+        text: V5Text,
+        location: TextLocation,
+        #[serde(default)]
+        opaque: Option<bool>,
+        background: BackgroundRepr,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BackgroundRepr {
+    Current(TextBackground),
+    Legacy(Color),
+}
+
+impl From<DrawCommandRepr> for DrawCommand {
+    fn from(repr: DrawCommandRepr) -> Self {
+        match repr {
+            DrawCommandRepr::Fill { shape } => Self::Fill { shape },
+            DrawCommandRepr::Stroke { shape } => Self::Stroke { shape },
+            DrawCommandRepr::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            } => Self::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            },
+            DrawCommandRepr::Write {
+                text,
+                location,
+                opaque,
+                background,
+            } => Self::Write {
+                text,
+                location,
+                background: match background {
+                    BackgroundRepr::Current(background) => background,
+                    BackgroundRepr::Legacy(color) if opaque != Some(false) => {
+                        TextBackground::Solid(color)
+                    }
+                    BackgroundRepr::Legacy(_) => TextBackground::Transparent,
+                },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum TextLocation {
+    #[serde(rename = "Coordinates")]
+    Coordinates { point: Point2<i32> },
+    #[serde(rename = "Line")]
+    Line { line: i32 },
+}
+
+impl Default for TextLocation {
+    fn default() -> Self {
+        Self::Coordinates {
+            point: Point2 { x: 0, y: 0 },
+        }
+    }
+}
+
+/// The region of the screen moved by a scroll.
+///
+/// A scroll by `lines` pixels moves the contents of the region up when `lines` is positive and
+/// down when it is negative. Pixels moved outside of the region are discarded, and pixels left
+/// uncovered are filled with the scroll's background color. Regions extending past the edges
+/// of the screen are clamped to the screen. [`ScrollLocation::regions`] computes the areas
+/// affected by a scroll.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum ScrollLocation {
+    /// Every pixel from the top of the given text line to the bottom of the screen.
+    ///
+    /// Line `n` starts at `y = n * LINE_HEIGHT`.
+    #[serde(rename = "Line")]
+    Line { line: i32 },
+    #[serde(rename = "Rectangle")]
+    Rectangle {
+        top_left: Point2<i32>,
+        bottom_right: Point2<i32>,
+    },
+}
+
+impl ScrollLocation {
+    /// The region of the screen this location refers to, clamped to the screen, or `None` if it
+    /// does not overlap the screen.
+    pub fn rect(&self) -> Option<Rect> {
+        let (top_left, bottom_right) = match *self {
+            Self::Line { line } => (
+                Point2 {
+                    x: 0,
+                    y: line.saturating_mul(LINE_HEIGHT),
+                },
+                Point2 {
+                    x: SCREEN_WIDTH as i32 - 1,
+                    y: SCREEN_HEIGHT as i32 - 1,
+                },
+            ),
+            Self::Rectangle {
+                top_left,
+                bottom_right,
+            } => (top_left, bottom_right),
+        };
+        let rect = Rect {
+            top_left: Point2 {
+                x: top_left.x.max(0),
+                y: top_left.y.max(0),
+            },
+            bottom_right: Point2 {
+                x: bottom_right.x.min(SCREEN_WIDTH as i32 - 1),
+                y: bottom_right.y.min(SCREEN_HEIGHT as i32 - 1),
+            },
+        };
+        (rect.top_left.x <= rect.bottom_right.x && rect.top_left.y <= rect.bottom_right.y)
+            .then_some(rect)
+    }
+
+    /// Splits a scroll by `lines` pixels into the pixels that are moved and the pixels that are
+    /// filled with the background color, or returns `None` if the region is empty.
+    pub fn regions(&self, lines: i32) -> Option<ScrollRegions> {
+        let region = self.rect()?;
+        let (top, bottom) = (region.top_left.y, region.bottom_right.y);
+        let rows = |top: i32, bottom: i32| Rect {
+            top_left: Point2 {
+                x: region.top_left.x,
+                y: top,
+            },
+            bottom_right: Point2 {
+                x: region.bottom_right.x,
+                y: bottom,
+            },
+        };
+
+        let distance = lines.unsigned_abs();
+        let regions = if distance > (bottom - top) as u32 {
+            ScrollRegions {
+                region,
+                source: None,
+                destination: None,
+                fill: Some(region),
+            }
+        } else if lines > 0 {
+            ScrollRegions {
+                region,
+                source: Some(rows(top + lines, bottom)),
+                destination: Some(rows(top, bottom - lines)),
+                fill: Some(rows(bottom - lines + 1, bottom)),
+            }
+        } else if lines < 0 {
+            ScrollRegions {
+                region,
+                source: Some(rows(top, bottom + lines)),
+                destination: Some(rows(top - lines, bottom)),
+                fill: Some(rows(top, top - lines - 1)),
+            }
+        } else {
+            ScrollRegions {
+                region,
+                source: Some(region),
+                destination: Some(region),
+                fill: None,
+            }
+        };
+        Some(regions)
+    }
+}
+
+/// The areas of the screen affected by a scroll, in screen coordinates.
+///
+/// `source` and `destination` always have the same size, and are both `None` when the scroll
+/// distance is at least the height of the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ScrollRegions {
+    /// The whole scrolled region, clamped to the screen.
+    pub region: Rect,
+    /// The pixels that are moved.
+    pub source: Option<Rect>,
+    /// Where the moved pixels end up.
+    pub destination: Option<Rect>,
+    /// The pixels filled with the background color.
+    pub fill: Option<Rect>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub struct V5Text {
+    /// The text to draw.
+    ///
+    /// Text must not contain control characters; see [`escape_text`] for how they are represented.
+    pub data: String,
+    pub font_family: V5FontFamily,
+    pub font_size: V5FontSize,
+}
+
+impl V5Text {
+    /// Creates text from bytes written by a user program.
+    ///
+    /// Invalid UTF-8 is replaced with U+FFFD and control characters are escaped with [`escape_text`].
+    pub fn from_bytes_lossy(
+        bytes: &[u8],
+        font_family: V5FontFamily,
+        font_size: V5FontSize,
+    ) -> Self {
+        Self {
+            data: escape_text(&String::from_utf8_lossy(bytes)).into_owned(),
+            font_family,
+            font_size,
+        }
+    }
+
+    /// Creates text from bytes written by a user program, failing if they are not
+    /// valid UTF-8 or contain control characters.
+    pub fn from_bytes_strict(
+        bytes: &[u8],
+        font_family: V5FontFamily,
+        font_size: V5FontSize,
+    ) -> Result<Self, TextError> {
+        let data = std::str::from_utf8(bytes).map_err(|err| TextError::InvalidUtf8 {
+            valid_up_to: err.valid_up_to(),
+        })?;
+        let text = Self {
+            data: data.to_owned(),
+            font_family,
+            font_size,
+        };
+        text.validate()?;
+        Ok(text)
+    }
+
+    /// Checks that this text contains no control characters.
+    ///
+    /// Receivers that want to reject malformed text rather than render it should call this after decoding.
+    pub fn validate(&self) -> Result<(), TextError> {
+        match self.data.char_indices().find(|(_, c)| c.is_control()) {
+            Some((index, character)) => Err(TextError::ControlCharacter { index, character }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Replaces control characters in `text` with printable stand-ins.
+///
+/// C0 control characters (including newlines and tabs) and DEL become their
+/// corresponding Unicode Control Pictures (U+2400 through U+2421), and C1 control
+/// characters become U+FFFD. This keeps text drawn to the screen on a single line
+/// and makes unprintable output visible rather than silently dropped.
+pub fn escape_text(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(char::is_control) {
+        return Cow::Borrowed(text);
+    }
+
+    Cow::Owned(
+        text.chars()
+            .map(|c| match c {
+                '\0'..='\x1f' => char::from_u32(0x2400 + c as u32).unwrap(),
+                '\x7f' => '\u{2421}',
+                c if c.is_control() => char::REPLACEMENT_CHARACTER,
+                c => c,
+            })
+            .collect(),
+    )
+}
+
+/// An error returned when text does not follow the protocol's text encoding rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextError {
+    /// The text was not valid UTF-8.
+    InvalidUtf8 { valid_up_to: usize },
+    /// The text contained a control character.
+    ControlCharacter { index: usize, character: char },
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUtf8 { valid_up_to } => {
+                write!(f, "invalid UTF-8 after byte {valid_up_to}")
+            }
+            Self::ControlCharacter { index, character } => {
+                write!(f, "control character {character:?} at byte {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextError {}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub enum V5FontFamily {
+    #[default]
+    #[serde(rename = "UserMono")]
+    UserMono,
+    #[serde(rename = "TimerMono")]
+    TimerMono,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub enum V5FontSize {
+    #[serde(rename = "Small")]
+    Small,
+    #[default]
+    #[serde(rename = "Normal")]
+    Normal,
+    #[serde(rename = "Large")]
+    Large,
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord,
+)]
+pub struct TextMetrics {
+    pub width: usize,
+    pub height: usize,
+}
+
+/// A shape that can be drawn to the robot LCD screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Shape {
+    #[serde(rename = "Rectangle")]
+    Rectangle {
+        top_left: Point2<i32>,
+        bottom_right: Point2<i32>,
+    },
+    #[serde(rename = "Circle")]
+    Circle { center: Point2<i32>, radius: u16 },
+    #[serde(rename = "Pixel")]
+    Pixel { pos: Point2<i32> },
+    #[serde(rename = "Line")]
+    Line {
+        start: Point2<i32>,
+        end: Point2<i32>,
+    },
+}
+
+/// The gearset of a VEX V5 motor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum TouchEvent {
+    #[serde(rename = "Released")]
+    Released,
+    #[serde(rename = "Pressed")]
+    Pressed,
+    #[serde(rename = "Held")]
+    Held,
+}
+
+/// A 24-bit RGB color stored as `0x00RRGGBB`.
+///
+/// Colors are serialized as integers, but can be deserialized from either an integer or a
+/// `"#RRGGBB"` string. Implementations should only send integers, since older peers don't
+/// accept strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Default)]
+pub struct Color(pub u32);
+
+impl Color {
+    /// Parses a color in `"#RRGGBB"` form.
+    pub fn from_hex(hex: &str) -> Result<Self, ParseColorError> {
+        let digits = hex.strip_prefix('#').ok_or(ParseColorError)?;
+        if digits.len() != 6 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError);
+        }
+        u32::from_str_radix(digits, 16)
+            .map(Self)
+            .map_err(|_| ParseColorError)
+    }
+
+    /// Formats this color in `"#RRGGBB"` form.
+    pub fn to_hex(&self) -> String {
+        format!("#{:06X}", self.0 & 0x00FF_FFFF)
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColorVisitor;
+
+        impl Visitor<'_> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an integer or a \"#RRGGBB\" string")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Color, E> {
+                u32::try_from(value)
+                    .map(Color)
+                    .map_err(|_| E::invalid_value(Unexpected::Unsigned(value), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Color, E> {
+                u32::try_from(value)
+                    .map(Color)
+                    .map_err(|_| E::invalid_value(Unexpected::Signed(value), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Color, E> {
+                Color::from_hex(value).map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+/// An error returned when a string is not a color in `"#RRGGBB"` form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseColorError;
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expected a color in \"#RRGGBB\" form")
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+impl From<RGB8> for Color {
+    fn from(rgb: RGB8) -> Self {
+        Self(u32::from_be_bytes([0, rgb.r, rgb.g, rgb.b]))
+    }
+}
+
+impl From<Color> for RGB8 {
+    fn from(color: Color) -> Self {
+        let [_, r, g, b] = color.0.to_be_bytes();
+        RGB8 { r, g, b }
+    }
+}
+
+/// A situation in which the simulator automatically captures the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum ScreenshotTrigger {
+    /// User code panicked.
+    #[serde(rename = "Panic")]
+    Panic,
+    /// User code exited.
+    #[serde(rename = "Exit")]
+    Exit,
+    /// The competition mode changed.
+    #[serde(rename = "CompetitionModeChange")]
+    CompetitionModeChange,
+}
+
+/// An image of the robot LCD screen.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Screenshot {
+    pub width: u16,
+    pub height: u16,
+    /// Base64-encoded 8-bit RGB pixels in row-major order.
+    pub buffer: String,
+}
+
+impl Screenshot {
+    pub fn new(width: u16, height: u16, pixels: &[RGB8]) -> Self {
+        let bytes: Vec<u8> = pixels.iter().flat_map(|px| [px.r, px.g, px.b]).collect();
+        Self {
+            width,
+            height,
+            buffer: BASE64_STANDARD.encode(bytes),
+        }
+    }
+
+    /// Decodes the pixels, failing if the buffer is not valid base64 or does not hold a whole
+    /// number of pixels.
+    pub fn to_pixels(&self) -> Result<Vec<RGB8>, ScreenshotError> {
+        let bytes = BASE64_STANDARD
+            .decode(&self.buffer)
+            .map_err(ScreenshotError::Decode)?;
+        if bytes.len() % 3 != 0 {
+            return Err(ScreenshotError::PartialPixel { len: bytes.len() });
+        }
+        Ok(bytes
+            .chunks_exact(3)
+            .map(|px| RGB8::new(px[0], px[1], px[2]))
+            .collect())
+    }
+}
+
+/// An error returned when a [`Screenshot`]'s buffer cannot be decoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenshotError {
+    /// The buffer was not valid base64.
+    Decode(DecodeError),
+    /// The buffer decoded to `len` bytes, which is not a multiple of the 3 bytes in a pixel.
+    PartialPixel { len: usize },
+}
+
+impl fmt::Display for ScreenshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode(err) => write!(f, "{err}"),
+            Self::PartialPixel { len } => {
+                write!(
+                    f,
+                    "screenshot buffer of {len} bytes ends with a partial pixel"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScreenshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Decode(err) => Some(err),
+            Self::PartialPixel { .. } => None,
+        }
+    }
+}
+
+/// Rendering statistics measured by a frontend over one reporting period.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct FrameStats {
+    /// The number of frames rendered.
+    pub frames: u32,
+    /// The number of frames that were skipped because rendering fell behind.
+    pub dropped_frames: u32,
+    /// The mean time from receiving a [`ScreenRender`] event to presenting the frame, in
+    /// milliseconds.
+    ///
+    /// [`ScreenRender`]: crate::Event::ScreenRender
+    pub mean_latency_ms: f64,
+    /// The longest such time, in milliseconds.
+    pub max_latency_ms: f64,
+}
+
+/// How user code reads touches on the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum TouchListening {
+    /// User code has not read the touch state or registered a touch handler, so touches will
+    /// have no effect.
+    #[serde(rename = "None")]
+    None,
+    /// User code reads the touch state on demand, so short touches between reads may be missed.
+    #[serde(rename = "Polling")]
+    Polling,
+    /// User code has registered a handler that is called for each touch.
+    #[serde(rename = "Callbacks")]
+    Callbacks,
+}
+
+/// An additional display offered by the simulator, separate from the robot LCD screen, such as
+/// a dashboard of debugging visualizations.
+///
+/// Displays are drawn on with the same [`DrawCommand`]s as the robot screen, in a coordinate
+/// system with its origin at the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExtraDisplay {
+    /// Identifies the display in later events. IDs are not reused within a session.
+    pub id: u32,
+    /// A name for the display, suitable for showing to users.
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+}
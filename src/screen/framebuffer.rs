@@ -0,0 +1,401 @@
+use base64::prelude::*;
+use mint::Point2;
+use rgb::RGB8;
+
+use super::{Color, DrawCommand, Screenshot, ScrollLocation, Shape, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::{geometry::Rect, Event};
+
+const WIDTH: i32 = SCREEN_WIDTH as i32;
+const HEIGHT: i32 = SCREEN_HEIGHT as i32;
+
+/// A software copy of the robot LCD screen, updated by applying screen events.
+///
+/// Text drawn with [`DrawCommand::Write`] is not rendered, since doing so requires the V5's fonts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Framebuffer {
+    front: Vec<Color>,
+    back: Option<Vec<Color>>,
+}
+
+impl Framebuffer {
+    /// Creates a black framebuffer.
+    pub fn new() -> Self {
+        Self {
+            front: vec![Color(0); (WIDTH * HEIGHT) as usize],
+            back: None,
+        }
+    }
+
+    /// The pixels currently visible on the screen, in row-major order.
+    pub fn pixels(&self) -> &[Color] {
+        &self.front
+    }
+
+    /// The color of the visible pixel at `(x, y)`, or `None` if it is off the screen.
+    pub fn pixel(&self, x: i32, y: i32) -> Option<Color> {
+        index(i64::from(x), i64::from(y)).map(|index| self.front[index])
+    }
+
+    /// Captures the visible pixels as a [`Screenshot`].
+    pub fn screenshot(&self) -> Screenshot {
+        let pixels: Vec<RGB8> = self.front.iter().map(|&color| color.into()).collect();
+        Screenshot::new(SCREEN_WIDTH as u16, SCREEN_HEIGHT as u16, &pixels)
+    }
+
+    /// Updates the framebuffer to reflect a screen event.
+    ///
+    /// Events that do not affect the screen are ignored.
+    pub fn apply(&mut self, event: &Event) {
+        match event {
+            Event::ScreenDraw {
+                command,
+                color,
+                clip_region,
+            } => self.draw(command, *color, *clip_region),
+            Event::ScreenScroll {
+                location,
+                lines,
+                background,
+                clip_region,
+            } => self.scroll(location, *lines, *background, *clip_region),
+            Event::ScreenClear { color, clip_region } => {
+                let mut target = self.target(*clip_region);
+                target.fill_rect(clip_region.top_left, clip_region.bottom_right, *color);
+            }
+            Event::ScreenDoubleBufferMode { enable } => {
+                self.back = enable.then(|| self.front.clone());
+            }
+            Event::ScreenRender => {
+                if let Some(back) = &self.back {
+                    self.front.clone_from(back);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn target(&mut self, clip: Rect) -> Target<'_> {
+        Target {
+            pixels: self.back.as_mut().unwrap_or(&mut self.front),
+            clip,
+        }
+    }
+
+    fn draw(&mut self, command: &DrawCommand, color: Color, clip: Rect) {
+        let mut target = self.target(clip);
+        match command {
+            DrawCommand::Fill { shape } => match *shape {
+                Shape::Rectangle {
+                    top_left,
+                    bottom_right,
+                } => target.fill_rect(top_left, bottom_right, color),
+                Shape::Circle { center, radius } => {
+                    let (cx, cy, radius) =
+                        (i64::from(center.x), i64::from(center.y), i64::from(radius));
+                    let Some(area) = target.bounds().intersect(Area {
+                        left: cx - radius,
+                        top: cy - radius,
+                        right: cx + radius,
+                        bottom: cy + radius,
+                    }) else {
+                        return;
+                    };
+                    for y in area.top..=area.bottom {
+                        let dy = y - cy;
+                        let dx = ((radius * radius - dy * dy) as f64).sqrt() as i64;
+                        for x in (cx - dx).max(area.left)..=(cx + dx).min(area.right) {
+                            target.set(x, y, color);
+                        }
+                    }
+                }
+                shape => target.stroke(shape, color),
+            },
+            DrawCommand::Stroke { shape } => target.stroke(*shape, color),
+            DrawCommand::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            } => {
+                // Malformed buffers are ignored, like other invalid draw commands.
+                let Ok(bytes) = BASE64_STANDARD.decode(buffer) else {
+                    return;
+                };
+                let pixels: Vec<u32> = bytes
+                    .chunks_exact(4)
+                    .map(|px| u32::from_le_bytes([px[0], px[1], px[2], px[3]]))
+                    .collect();
+                let stride = i64::from(stride.get());
+                let (left, top) = (i64::from(top_left.x), i64::from(top_left.y));
+                let Some(area) = target
+                    .bounds()
+                    .intersect(Area::new(*top_left, *bottom_right))
+                else {
+                    return;
+                };
+                for y in area.top..=area.bottom {
+                    for x in area.left..=area.right {
+                        let offset = (y - top) * stride + (x - left);
+                        if let Some(&pixel) = pixels.get(offset as usize) {
+                            target.set(x, y, Color(pixel & 0x00FF_FFFF));
+                        }
+                    }
+                }
+            }
+            DrawCommand::Write { .. } => {}
+        }
+    }
+
+    fn scroll(&mut self, location: &ScrollLocation, lines: i32, background: Color, clip: Rect) {
+        let Some(regions) = location.regions(lines) else {
+            return;
+        };
+
+        let mut target = self.target(clip);
+        if let (Some(source), Some(destination)) = (regions.source, regions.destination) {
+            let pixels = target.pixels.clone();
+            let (dx, dy) = (
+                source.top_left.x - destination.top_left.x,
+                source.top_left.y - destination.top_left.y,
+            );
+            for y in destination.top_left.y..=destination.bottom_right.y {
+                for x in destination.top_left.x..=destination.bottom_right.x {
+                    if let Some(index) = index(i64::from(x + dx), i64::from(y + dy)) {
+                        target.set(i64::from(x), i64::from(y), pixels[index]);
+                    }
+                }
+            }
+        }
+        if let Some(fill) = regions.fill {
+            target.fill_rect(fill.top_left, fill.bottom_right, background);
+        }
+    }
+}
+
+impl Default for Framebuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn index(x: i64, y: i64) -> Option<usize> {
+    SCREEN
+        .contains(x, y)
+        .then(|| (y * i64::from(WIDTH) + x) as usize)
+}
+
+/// The whole screen, as an [`Area`].
+const SCREEN: Area = Area {
+    left: 0,
+    top: 0,
+    right: WIDTH as i64 - 1,
+    bottom: HEIGHT as i64 - 1,
+};
+
+/// A rectangle with inclusive corners, in coordinates wide enough that shapes far off the
+/// screen can be clipped without overflowing.
+#[derive(Debug, Clone, Copy)]
+struct Area {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Area {
+    fn new(top_left: Point2<i32>, bottom_right: Point2<i32>) -> Self {
+        Self {
+            left: top_left.x.into(),
+            top: top_left.y.into(),
+            right: bottom_right.x.into(),
+            bottom: bottom_right.y.into(),
+        }
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        (self.left..=self.right).contains(&x) && (self.top..=self.bottom).contains(&y)
+    }
+
+    /// The overlap of two areas, or `None` if they do not overlap.
+    fn intersect(self, other: Area) -> Option<Area> {
+        let area = Area {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        };
+        (area.left <= area.right && area.top <= area.bottom).then_some(area)
+    }
+}
+
+/// A buffer being drawn to, with a clip region applied.
+///
+/// Every drawing operation is limited to the part of the screen inside the clip region before
+/// any pixels are visited, so shapes with enormous coordinates cost no more to draw than ones
+/// that fit on the screen.
+struct Target<'a> {
+    pixels: &'a mut Vec<Color>,
+    clip: Rect,
+}
+
+impl Target<'_> {
+    /// The part of the screen that may be drawn to. Empty clip regions produce an area that
+    /// contains no pixels.
+    fn bounds(&self) -> Area {
+        SCREEN
+            .intersect(Area::new(self.clip.top_left, self.clip.bottom_right))
+            .unwrap_or(Area {
+                left: 0,
+                top: 0,
+                right: -1,
+                bottom: -1,
+            })
+    }
+
+    fn set(&mut self, x: i64, y: i64, color: Color) {
+        if let (true, Some(index)) = (self.bounds().contains(x, y), index(x, y)) {
+            self.pixels[index] = color;
+        }
+    }
+
+    fn fill_rect(&mut self, top_left: Point2<i32>, bottom_right: Point2<i32>, color: Color) {
+        let Some(area) = self.bounds().intersect(Area::new(top_left, bottom_right)) else {
+            return;
+        };
+        for y in area.top..=area.bottom {
+            for x in area.left..=area.right {
+                self.set(x, y, color);
+            }
+        }
+    }
+
+    fn line(&mut self, start: Point2<i32>, end: Point2<i32>, color: Color) {
+        let Some(((x0, y0), (x1, y1))) = clip_line(self.bounds(), start, end) else {
+            return;
+        };
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (sx, sy) = ((x1 - x0).signum(), (y1 - y0).signum());
+        let (mut x, mut y, mut err) = (x0, y0, dx + dy);
+        loop {
+            self.set(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn stroke(&mut self, shape: Shape, color: Color) {
+        match shape {
+            Shape::Rectangle {
+                top_left,
+                bottom_right,
+            } => {
+                let top_right = Point2 {
+                    x: bottom_right.x,
+                    y: top_left.y,
+                };
+                let bottom_left = Point2 {
+                    x: top_left.x,
+                    y: bottom_right.y,
+                };
+                self.line(top_left, top_right, color);
+                self.line(top_right, bottom_right, color);
+                self.line(bottom_right, bottom_left, color);
+                self.line(bottom_left, top_left, color);
+            }
+            Shape::Circle { center, radius } => {
+                let (cx, cy, radius) =
+                    (i64::from(center.x), i64::from(center.y), i64::from(radius));
+                let outline = Area {
+                    left: cx - radius,
+                    top: cy - radius,
+                    right: cx + radius,
+                    bottom: cy + radius,
+                };
+                if self.bounds().intersect(outline).is_none() {
+                    return;
+                }
+                // The midpoint algorithm visits about 0.7 * radius points, which is bounded
+                // because the radius is a u16.
+                let (mut x, mut y, mut err) = (radius, 0, 1 - radius);
+                while x >= y {
+                    for (px, py) in [
+                        (x, y),
+                        (y, x),
+                        (-y, x),
+                        (-x, y),
+                        (-x, -y),
+                        (-y, -x),
+                        (y, -x),
+                        (x, -y),
+                    ] {
+                        self.set(cx + px, cy + py, color);
+                    }
+                    y += 1;
+                    if err < 0 {
+                        err += 2 * y + 1;
+                    } else {
+                        x -= 1;
+                        err += 2 * (y - x) + 1;
+                    }
+                }
+            }
+            Shape::Pixel { pos } => self.set(pos.x.into(), pos.y.into(), color),
+            Shape::Line { start, end } => self.line(start, end, color),
+        }
+    }
+}
+
+/// Clips the line from `start` to `end` to `area` using the Liang-Barsky algorithm, returning
+/// the endpoints of the visible part, or `None` if none of it is visible.
+///
+/// Lines that lie entirely within `area` are returned unchanged, so they are drawn exactly as
+/// they would be without clipping.
+fn clip_line(area: Area, start: Point2<i32>, end: Point2<i32>) -> Option<((i64, i64), (i64, i64))> {
+    let (x0, y0) = (i64::from(start.x), i64::from(start.y));
+    let (x1, y1) = (i64::from(end.x), i64::from(end.y));
+    if area.contains(x0, y0) && area.contains(x1, y1) {
+        return Some(((x0, y0), (x1, y1)));
+    }
+
+    let (dx, dy) = ((x1 - x0) as f64, (y1 - y0) as f64);
+    let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+    for (p, q) in [
+        (-dx, (x0 - area.left) as f64),
+        (dx, (area.right - x0) as f64),
+        (-dy, (y0 - area.top) as f64),
+        (dy, (area.bottom - y0) as f64),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+            if p < 0.0 {
+                t0 = t0.max(t);
+            } else {
+                t1 = t1.min(t);
+            }
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    let point = |t: f64| {
+        (
+            (x0 as f64 + t * dx).round() as i64,
+            (y0 as f64 + t * dy).round() as i64,
+        )
+    };
+    Some((point(t0), point(t1)))
+}
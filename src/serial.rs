@@ -0,0 +1,190 @@
+//! Serial output from user programs.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use base64::{prelude::*, DecodeError};
+use serde::{Deserialize, Serialize};
+
+use crate::devices::SmartPort;
+
+/// Bytes sent over a serial channel.
+///
+/// Serial data is base64-encoded so that arbitrary binary output, including
+/// invalid UTF-8 and newlines, can be sent without affecting message framing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SerialData {
+    pub channel: u32,
+    pub data: String,
+}
+
+impl SerialData {
+    pub fn new(channel: u32, bytes: &[u8]) -> Self {
+        Self {
+            channel,
+            data: BASE64_STANDARD.encode(bytes),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        BASE64_STANDARD.decode(&self.data)
+    }
+}
+
+/// Bytes sent over a smart port in generic serial mode.
+///
+/// Like [`SerialData`], the bytes are base64-encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct PortSerialData {
+    pub port: SmartPort,
+    pub data: String,
+}
+
+impl PortSerialData {
+    pub fn new(port: SmartPort, bytes: &[u8]) -> Self {
+        Self {
+            port,
+            data: BASE64_STANDARD.encode(bytes),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        BASE64_STANDARD.decode(&self.data)
+    }
+}
+
+/// The connection that carries serial data between the brain and the computer.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum SerialPath {
+    /// A USB cable plugged directly into the brain.
+    #[default]
+    #[serde(rename = "Direct")]
+    Direct,
+    /// A USB cable plugged into a controller, which relays data to the brain over its radio.
+    #[serde(rename = "Controller")]
+    Controller,
+}
+
+impl SerialPath {
+    /// The maximum rate at which serial data can be sent over this path, in bits per second,
+    /// or `None` if the path is effectively unlimited.
+    ///
+    /// Simulators should throttle serial output to this rate.
+    pub fn bandwidth(&self) -> Option<u32> {
+        match self {
+            Self::Direct => None,
+            Self::Controller => Some(230_400),
+        }
+    }
+}
+
+/// Buffers serial output and groups it into [`SerialData`] messages of a reasonable size.
+///
+/// Sending a message for every print, or every byte, makes serial data dominate the message
+/// stream for chatty programs. A `SerialWriter` instead holds bytes for each channel until a
+/// full chunk is available or the oldest buffered byte has waited for the maximum delay.
+#[derive(Debug, Clone)]
+pub struct SerialWriter {
+    chunk_size: usize,
+    max_delay: Duration,
+    pending: BTreeMap<u32, PendingSerial>,
+}
+
+#[derive(Debug, Clone)]
+struct PendingSerial {
+    bytes: Vec<u8>,
+    since: Instant,
+}
+
+impl SerialWriter {
+    /// Creates a writer that sends chunks of up to 4 KiB and holds bytes for up to 10 ms.
+    pub fn new() -> Self {
+        Self {
+            chunk_size: 4096,
+            max_delay: Duration::from_millis(10),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the largest number of bytes sent in a single message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is zero.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "serial chunk size must be positive");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Sets the longest time bytes are held before being sent in a partial chunk.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Buffers bytes written to `channel`, returning any chunks that are now full.
+    pub fn write(&mut self, channel: u32, bytes: &[u8]) -> Vec<SerialData> {
+        let pending = self
+            .pending
+            .entry(channel)
+            .or_insert_with(|| PendingSerial {
+                bytes: Vec::new(),
+                since: Instant::now(),
+            });
+        pending.bytes.extend_from_slice(bytes);
+
+        let mut messages = Vec::new();
+        while pending.bytes.len() >= self.chunk_size {
+            let rest = pending.bytes.split_off(self.chunk_size);
+            messages.push(SerialData::new(channel, &pending.bytes));
+            pending.bytes = rest;
+            pending.since = Instant::now();
+        }
+        if pending.bytes.is_empty() {
+            self.pending.remove(&channel);
+        }
+        messages
+    }
+
+    /// Returns the buffered bytes of every channel whose oldest byte has waited for at least
+    /// the maximum delay.
+    ///
+    /// This should be called regularly, such as once per simulation tick.
+    pub fn poll(&mut self) -> Vec<SerialData> {
+        let now = Instant::now();
+        let mut messages = Vec::new();
+        self.pending.retain(|&channel, pending| {
+            if now.duration_since(pending.since) < self.max_delay {
+                return true;
+            }
+            messages.push(SerialData::new(channel, &pending.bytes));
+            false
+        });
+        messages
+    }
+
+    /// Returns all buffered bytes, such as before user code exits.
+    pub fn flush(&mut self) -> Vec<SerialData> {
+        let pending = std::mem::take(&mut self.pending);
+        pending
+            .into_iter()
+            .map(|(channel, pending)| SerialData::new(channel, &pending.bytes))
+            .collect()
+    }
+
+    /// Whether any bytes are waiting to be sent.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl Default for SerialWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
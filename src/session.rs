@@ -0,0 +1,45 @@
+//! A single description of a negotiated session, for tooling downstream of the connection.
+
+use serde::{Deserialize, Serialize};
+
+use crate::Implementation;
+
+/// What was agreed during a session's handshake, and who took part in it.
+///
+/// Session info can be written into recordings and logs, and read back to find out how a
+/// session was configured. Frontends and simulators can create it from the outcome of a
+/// handshake with [`Negotiated::session_info`](crate::codec::Negotiated::session_info).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SessionInfo {
+    /// The protocol version both sides speak.
+    pub version: i32,
+    /// The extensions advertised by both sides.
+    pub capabilities: Vec<String>,
+    pub implementations: Implementations,
+    /// The time the session started, in milliseconds since the Unix epoch.
+    pub start_time: u64,
+}
+
+impl SessionInfo {
+    /// Creates session info for a session without any extensions or known implementations.
+    pub fn new(version: i32, start_time: u64) -> Self {
+        Self {
+            version,
+            capabilities: Vec::new(),
+            implementations: Implementations::default(),
+            start_time,
+        }
+    }
+
+    /// Whether both sides advertised `extension`.
+    pub fn has_capability(&self, extension: &str) -> bool {
+        self.capabilities.iter().any(|ext| ext == extension)
+    }
+}
+
+/// The simulator and frontend taking part in a session, where known.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Implementations {
+    pub simulator: Option<Implementation>,
+    pub frontend: Option<Implementation>,
+}
@@ -0,0 +1,192 @@
+//! Tracking the current state of a simulated robot from its message stream.
+
+use std::collections::BTreeMap;
+
+use mint::Point2;
+
+use crate::{
+    competition::{CompetitionMode, CompetitionPhase},
+    devices::{Battery, Device, DeviceStatus, MotorLimits, Port, SmartPort},
+    Command, Event,
+};
+
+/// The state of a simulated robot, as seen by the simulator.
+///
+/// The tracker is updated by feeding it every event and command sent during a session.
+#[derive(Debug, Clone, Default, PartialEq, PartialOrd)]
+pub struct RobotStateTracker {
+    configs: BTreeMap<Port, Device>,
+    statuses: BTreeMap<Port, DeviceStatus>,
+    claims: BTreeMap<Port, String>,
+    motor_limits: BTreeMap<SmartPort, MotorLimits>,
+    adi_inputs: BTreeMap<Port, f64>,
+    adi_outputs: BTreeMap<Port, bool>,
+    adi_analog_outputs: BTreeMap<Port, f64>,
+    battery: Option<Battery>,
+    pose: Option<Point2<f64>>,
+    competition_mode: CompetitionMode,
+    /// The running competition schedule, and how long ago it was started.
+    schedule: Vec<CompetitionPhase>,
+    schedule_elapsed_ms: u64,
+    started: bool,
+    exited: bool,
+}
+
+impl RobotStateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the state to reflect an event sent by the simulator.
+    pub fn apply_event(&mut self, event: &Event) {
+        match event {
+            Event::DeviceUpdate { status, port } => {
+                self.statuses.insert(*port, status.clone());
+            }
+            Event::DeviceDelta { port, delta } => {
+                if let Some(status) = self.statuses.get_mut(port) {
+                    // A delta that doesn't fit the previous status is dropped, like any other
+                    // malformed update.
+                    if let Ok(updated) = crate::delta::apply(status, delta) {
+                        *status = updated;
+                    }
+                }
+            }
+            Event::Battery(battery) => self.battery = Some(battery.clone()),
+            Event::RobotPose { x, y } => self.pose = Some(Point2 { x: *x, y: *y }),
+            Event::AdiOutput { port, high } => {
+                self.adi_outputs.insert(*port, *high);
+            }
+            Event::AdiAnalogOutput { port, voltage } => {
+                self.adi_analog_outputs.insert(*port, *voltage);
+            }
+            Event::MotorLimits { port, limits } => {
+                self.motor_limits.insert(*port, *limits);
+            }
+            Event::PortClaimed { port, kind } => {
+                self.claims.insert(*port, kind.clone());
+            }
+            Event::Exited => self.exited = true,
+            _ => {}
+        }
+    }
+
+    /// Updates the state to reflect a command sent by the frontend.
+    pub fn apply_command(&mut self, command: &Command) {
+        match command {
+            Command::ConfigureDevice { port, device } => {
+                self.configs.insert(*port, *device);
+            }
+            Command::ConfigureDevices(configs) => {
+                self.configs
+                    .extend(configs.iter().map(|config| (config.port, config.device)));
+            }
+            Command::AdiInput {
+                port,
+                voltage,
+                expander,
+            } => {
+                self.adi_inputs
+                    .insert(Port::adi(*expander, *port), *voltage);
+            }
+            Command::CompetitionMode(mode) => {
+                self.competition_mode = *mode;
+                self.schedule.clear();
+            }
+            Command::CompetitionSchedule(schedule) => {
+                self.schedule = schedule.clone();
+                self.schedule_elapsed_ms = 0;
+                self.advance(0);
+            }
+            Command::StartExecution | Command::RunProgram { .. } => self.started = true,
+            _ => {}
+        }
+    }
+
+    /// Moves the tracker's clock forward by `elapsed_ms` milliseconds, applying the mode of
+    /// each phase of a running [`Command::CompetitionSchedule`] as it takes effect.
+    pub fn advance(&mut self, elapsed_ms: u64) {
+        self.schedule_elapsed_ms = self.schedule_elapsed_ms.saturating_add(elapsed_ms);
+        if let Some(mode) = CompetitionPhase::mode_at(&self.schedule, self.schedule_elapsed_ms) {
+            self.competition_mode = mode;
+        }
+    }
+
+    /// The configuration of the device on `port`.
+    pub fn config(&self, port: Port) -> Option<&Device> {
+        self.configs.get(&port)
+    }
+
+    /// The configuration of every configured device.
+    pub fn configs(&self) -> &BTreeMap<Port, Device> {
+        &self.configs
+    }
+
+    /// The latest status of the device on `port`.
+    pub fn status(&self, port: Port) -> Option<&DeviceStatus> {
+        self.statuses.get(&port)
+    }
+
+    /// The latest status of every device that has reported one.
+    pub fn statuses(&self) -> &BTreeMap<Port, DeviceStatus> {
+        &self.statuses
+    }
+
+    /// The limits user code has set on the motor on `port`, which are the defaults if it has
+    /// not set any.
+    pub fn motor_limits(&self, port: SmartPort) -> MotorLimits {
+        self.motor_limits.get(&port).copied().unwrap_or_default()
+    }
+
+    /// The kind of device user code has created on `port`.
+    pub fn claim(&self, port: Port) -> Option<&str> {
+        self.claims.get(&port).map(String::as_str)
+    }
+
+    /// The ports where user code has created a different kind of device than the one
+    /// configured, or created a device on a port with nothing configured.
+    pub fn mismatched_ports(&self) -> impl Iterator<Item = Port> + '_ {
+        self.claims.iter().filter_map(|(port, kind)| {
+            let configured = self.configs.get(port).map(Device::kind);
+            (configured != Some(kind.as_str())).then_some(*port)
+        })
+    }
+
+    /// The latest voltage sent to the ADI port `port`, in volts.
+    pub fn adi_input(&self, port: Port) -> Option<f64> {
+        self.adi_inputs.get(&port).copied()
+    }
+
+    /// Whether user code is driving the ADI digital output on `port` high.
+    pub fn adi_output(&self, port: Port) -> Option<bool> {
+        self.adi_outputs.get(&port).copied()
+    }
+
+    /// The voltage user code is driving the ADI analog output on `port` to, in volts.
+    pub fn adi_analog_output(&self, port: Port) -> Option<f64> {
+        self.adi_analog_outputs.get(&port).copied()
+    }
+
+    pub fn battery(&self) -> Option<&Battery> {
+        self.battery.as_ref()
+    }
+
+    /// The latest position of the robot.
+    pub fn pose(&self) -> Option<Point2<f64>> {
+        self.pose
+    }
+
+    pub fn competition_mode(&self) -> &CompetitionMode {
+        &self.competition_mode
+    }
+
+    /// Whether the frontend has started running user code.
+    pub fn started(&self) -> bool {
+        self.started
+    }
+
+    /// Whether user code has exited.
+    pub fn exited(&self) -> bool {
+        self.exited
+    }
+}
@@ -0,0 +1,139 @@
+//! Bandwidth and throughput statistics for a message stream.
+
+use std::{
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+use crate::{Command, Event};
+
+/// Totals for a single kind of message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct KindStats {
+    /// The number of messages observed.
+    pub count: u64,
+    /// The total encoded size of the observed messages, in bytes.
+    pub bytes: u64,
+}
+
+impl KindStats {
+    /// The average number of messages per second over `elapsed`.
+    pub fn message_rate(&self, elapsed: Duration) -> f64 {
+        rate(self.count, elapsed)
+    }
+
+    /// The average number of bytes per second over `elapsed`.
+    pub fn byte_rate(&self, elapsed: Duration) -> f64 {
+        rate(self.bytes, elapsed)
+    }
+}
+
+fn rate(total: u64, elapsed: Duration) -> f64 {
+    let secs = elapsed.as_secs_f64();
+    if secs == 0.0 {
+        0.0
+    } else {
+        total as f64 / secs
+    }
+}
+
+/// An accumulator that tracks message counts and sizes for each kind of [`Event`] and [`Command`].
+///
+/// Sizes are supplied by the caller so that they reflect the bytes actually sent over the transport.
+#[derive(Debug, Clone)]
+pub struct SessionStats {
+    started: Instant,
+    events: BTreeMap<&'static str, KindStats>,
+    commands: BTreeMap<&'static str, KindStats>,
+}
+
+impl SessionStats {
+    /// Creates an empty accumulator whose rates are measured from now.
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            events: BTreeMap::new(),
+            commands: BTreeMap::new(),
+        }
+    }
+
+    /// Records an event that was `size` bytes long when encoded.
+    pub fn record_event(&mut self, event: &Event, size: usize) {
+        record(&mut self.events, event.kind(), size);
+    }
+
+    /// Records a command that was `size` bytes long when encoded.
+    pub fn record_command(&mut self, command: &Command, size: usize) {
+        record(&mut self.commands, command.kind(), size);
+    }
+
+    /// The time since this accumulator was created.
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Totals for each kind of event that has been recorded.
+    pub fn events(&self) -> impl Iterator<Item = (&'static str, KindStats)> + '_ {
+        self.events.iter().map(|(kind, stats)| (*kind, *stats))
+    }
+
+    /// Totals for each kind of command that has been recorded.
+    pub fn commands(&self) -> impl Iterator<Item = (&'static str, KindStats)> + '_ {
+        self.commands.iter().map(|(kind, stats)| (*kind, *stats))
+    }
+
+    /// Totals for a single kind of event, such as `"ScreenDraw"`.
+    pub fn event(&self, kind: &str) -> KindStats {
+        self.events.get(kind).copied().unwrap_or_default()
+    }
+
+    /// Totals for a single kind of command, such as `"Touch"`.
+    pub fn command(&self, kind: &str) -> KindStats {
+        self.commands.get(kind).copied().unwrap_or_default()
+    }
+
+    /// Totals across every event that has been recorded.
+    pub fn total_events(&self) -> KindStats {
+        sum(self.events.values())
+    }
+
+    /// Totals across every command that has been recorded.
+    pub fn total_commands(&self) -> KindStats {
+        sum(self.commands.values())
+    }
+
+    /// Event kinds sorted by total size, largest first.
+    pub fn events_by_bytes(&self) -> Vec<(&'static str, KindStats)> {
+        by_bytes(&self.events)
+    }
+
+    /// Command kinds sorted by total size, largest first.
+    pub fn commands_by_bytes(&self) -> Vec<(&'static str, KindStats)> {
+        by_bytes(&self.commands)
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn record(map: &mut BTreeMap<&'static str, KindStats>, kind: &'static str, size: usize) {
+    let stats = map.entry(kind).or_default();
+    stats.count += 1;
+    stats.bytes += size as u64;
+}
+
+fn sum<'a>(stats: impl Iterator<Item = &'a KindStats>) -> KindStats {
+    stats.fold(KindStats::default(), |acc, stats| KindStats {
+        count: acc.count + stats.count,
+        bytes: acc.bytes + stats.bytes,
+    })
+}
+
+fn by_bytes(map: &BTreeMap<&'static str, KindStats>) -> Vec<(&'static str, KindStats)> {
+    let mut kinds: Vec<_> = map.iter().map(|(kind, stats)| (*kind, *stats)).collect();
+    kinds.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.bytes));
+    kinds
+}
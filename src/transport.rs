@@ -0,0 +1,438 @@
+//! Opening connections described by a URI, so that launchers can choose a transport with a
+//! single configuration string and both sides of a connection parse it the same way.
+//!
+//! The following schemes are supported:
+//!
+//! - `stdio:` uses the standard input and output of the current process.
+//! - `tcp://host:port` uses a TCP socket.
+//! - `unix:///path/to/socket` uses a Unix domain socket. Only available on Unix platforms.
+//! - `ws://host:port/path` uses a WebSocket, with each message sent as a text frame. Requires
+//!   the `websocket` feature.
+
+use std::{
+    cell::Cell,
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    str::FromStr,
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+use crate::codec::{ReadTimeout, Reader, Writer};
+
+/// The reading half of a [`Connection`]. Reads can time out on every transport, so the
+/// handshake helpers in [`codec`](crate::codec) can be used with any connection.
+pub trait ReadHalf: BufRead + ReadTimeout + Send {}
+
+impl<R: BufRead + ReadTimeout + Send> ReadHalf for R {}
+
+/// Both halves of an open connection.
+///
+/// The reader and writer are independent, so they can be moved to separate threads.
+pub struct Connection {
+    pub reader: Reader<Box<dyn ReadHalf>>,
+    pub writer: Writer<Box<dyn Write + Send>>,
+}
+
+impl Connection {
+    fn new(reader: impl ReadHalf + 'static, writer: impl Write + Send + 'static) -> Self {
+        Self {
+            reader: Reader::new(Box::new(reader)),
+            writer: Writer::new(Box::new(writer)),
+        }
+    }
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection").finish_non_exhaustive()
+    }
+}
+
+/// Where to open a connection, parsed from a URI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Endpoint {
+    Stdio,
+    /// A TCP socket at a `host:port` address.
+    Tcp(String),
+    /// A Unix domain socket at a path.
+    Unix(PathBuf),
+    /// A WebSocket at a `ws://` URI.
+    WebSocket(String),
+}
+
+/// An error returned when a URI does not describe a supported [`Endpoint`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ParseEndpointError {
+    uri: String,
+}
+
+impl fmt::Display for ParseEndpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsupported connection URI: {}", self.uri)
+    }
+}
+
+impl std::error::Error for ParseEndpointError {}
+
+impl From<ParseEndpointError> for io::Error {
+    fn from(err: ParseEndpointError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidInput, err)
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = ParseEndpointError;
+
+    fn from_str(uri: &str) -> Result<Self, Self::Err> {
+        let err = || ParseEndpointError {
+            uri: uri.to_string(),
+        };
+        if uri == "stdio:" || uri == "stdio://" {
+            Ok(Self::Stdio)
+        } else if let Some(address) = uri.strip_prefix("tcp://") {
+            if address.is_empty() {
+                return Err(err());
+            }
+            Ok(Self::Tcp(address.trim_end_matches('/').to_string()))
+        } else if let Some(path) = uri.strip_prefix("unix://") {
+            if path.is_empty() {
+                return Err(err());
+            }
+            Ok(Self::Unix(PathBuf::from(path)))
+        } else if uri.starts_with("ws://") {
+            Ok(Self::WebSocket(uri.to_string()))
+        } else {
+            Err(err())
+        }
+    }
+}
+
+impl Endpoint {
+    /// Connects to a peer listening at this endpoint.
+    pub fn connect(&self) -> io::Result<Connection> {
+        match self {
+            Self::Stdio => Ok(stdio()),
+            Self::Tcp(address) => {
+                let stream = TcpStream::connect(address)?;
+                Ok(Connection::new(BufReader::new(stream.try_clone()?), stream))
+            }
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                Ok(Connection::new(BufReader::new(stream.try_clone()?), stream))
+            }
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(uri) => websocket::connect(uri),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported(self)),
+        }
+    }
+
+    /// Listens at this endpoint and accepts a single connection from a peer.
+    pub fn accept(&self) -> io::Result<Connection> {
+        match self {
+            Self::Stdio => Ok(stdio()),
+            Self::Tcp(address) => {
+                let (stream, _) = TcpListener::bind(address)?.accept()?;
+                Ok(Connection::new(BufReader::new(stream.try_clone()?), stream))
+            }
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let (stream, _) = std::os::unix::net::UnixListener::bind(path)?.accept()?;
+                Ok(Connection::new(BufReader::new(stream.try_clone()?), stream))
+            }
+            #[cfg(feature = "websocket")]
+            Self::WebSocket(uri) => websocket::accept(uri),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported(self)),
+        }
+    }
+}
+
+/// Connects to a peer listening at the endpoint described by `uri`.
+pub fn connect(uri: &str) -> io::Result<Connection> {
+    uri.parse::<Endpoint>()?.connect()
+}
+
+/// Listens at the endpoint described by `uri` and accepts a single connection from a peer.
+pub fn accept(uri: &str) -> io::Result<Connection> {
+    uri.parse::<Endpoint>()?.accept()
+}
+
+fn stdio() -> Connection {
+    Connection::new(BufReader::new(StdinReader::spawn()), io::stdout())
+}
+
+/// Reads standard input on a background thread, since reads from the standard input handle
+/// itself cannot time out.
+///
+/// The thread reads ahead by a few chunks, so only one connection should use standard input
+/// at a time.
+struct StdinReader {
+    chunks: Receiver<io::Result<Vec<u8>>>,
+    chunk: Vec<u8>,
+    position: usize,
+    timeout: Cell<Option<Duration>>,
+}
+
+impl StdinReader {
+    fn spawn() -> Self {
+        let (sender, chunks) = mpsc::sync_channel(16);
+        thread::spawn(move || {
+            let mut stdin = io::stdin().lock();
+            loop {
+                let mut chunk = vec![0; 8 * 1024];
+                let result = match stdin.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(len) => {
+                        chunk.truncate(len);
+                        Ok(chunk)
+                    }
+                    Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(err) => Err(err),
+                };
+                let failed = result.is_err();
+                if sender.send(result).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self {
+            chunks,
+            chunk: Vec::new(),
+            position: 0,
+            timeout: Cell::new(None),
+        }
+    }
+}
+
+impl Read for StdinReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.chunk.len() {
+            let received = match self.timeout.get() {
+                Some(timeout) => self.chunks.recv_timeout(timeout),
+                None => self
+                    .chunks
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            self.chunk = match received {
+                Ok(chunk) => chunk?,
+                Err(RecvTimeoutError::Timeout) => return Err(io::ErrorKind::TimedOut.into()),
+                Err(RecvTimeoutError::Disconnected) => return Ok(0),
+            };
+            self.position = 0;
+        }
+        let len = buf.len().min(self.chunk.len() - self.position);
+        buf[..len].copy_from_slice(&self.chunk[self.position..self.position + len]);
+        self.position += len;
+        Ok(len)
+    }
+}
+
+impl ReadTimeout for StdinReader {
+    fn read_timeout(&self) -> io::Result<Option<Duration>> {
+        Ok(self.timeout.get())
+    }
+
+    fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+        self.timeout.set(timeout);
+        Ok(())
+    }
+}
+
+fn unsupported(endpoint: &Endpoint) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{endpoint:?} is not supported by this build"),
+    )
+}
+
+#[cfg(feature = "websocket")]
+mod websocket {
+    use std::{
+        cell::Cell,
+        io::{self, BufReader, Read, Write},
+        net::{TcpListener, TcpStream},
+        sync::{Arc, Mutex, MutexGuard, PoisonError},
+        time::{Duration, Instant},
+    };
+
+    use tungstenite::{Message, WebSocket};
+
+    use super::Connection;
+    use crate::codec::ReadTimeout;
+
+    /// How long the reader holds the socket while waiting for data before letting the writer
+    /// use it.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    pub fn connect(uri: &str) -> io::Result<Connection> {
+        let stream = TcpStream::connect(authority(uri))?;
+        let (socket, _) = tungstenite::client(uri, stream).map_err(io::Error::other)?;
+        split(socket)
+    }
+
+    pub fn accept(uri: &str) -> io::Result<Connection> {
+        let (stream, _) = TcpListener::bind(authority(uri))?.accept()?;
+        let socket = tungstenite::accept(stream).map_err(io::Error::other)?;
+        split(socket)
+    }
+
+    /// The `host:port` part of a `ws://` URI.
+    fn authority(uri: &str) -> String {
+        let rest = uri.trim_start_matches("ws://");
+        let authority = rest.split('/').next().unwrap_or(rest);
+        if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{authority}:80")
+        }
+    }
+
+    /// Splits a socket into halves that can be used from separate threads.
+    ///
+    /// Both halves share a single [`WebSocket`], so that replies to pings and close frames
+    /// received by the reader are never interleaved with frames sent by the writer. The reader
+    /// only holds the socket while data is arriving, and waits for more without it.
+    fn split(socket: WebSocket<TcpStream>) -> io::Result<Connection> {
+        let stream = socket.get_ref().try_clone()?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        let socket = Arc::new(Mutex::new(socket));
+        Ok(Connection::new(
+            BufReader::new(FrameReader {
+                socket: Arc::clone(&socket),
+                stream,
+                timeout: Cell::new(None),
+                buffer: Vec::new(),
+                position: 0,
+            }),
+            FrameWriter {
+                socket,
+                pending: Vec::new(),
+            },
+        ))
+    }
+
+    fn lock(socket: &Mutex<WebSocket<TcpStream>>) -> MutexGuard<'_, WebSocket<TcpStream>> {
+        socket.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether a read failed because no data arrived within the poll interval.
+    fn is_poll_timeout(err: &io::Error) -> bool {
+        matches!(
+            err.kind(),
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+        )
+    }
+
+    /// Presents incoming text frames as newline-delimited lines.
+    struct FrameReader {
+        socket: Arc<Mutex<WebSocket<TcpStream>>>,
+        /// A handle to the socket's stream, used to wait for data without locking the socket.
+        stream: TcpStream,
+        timeout: Cell<Option<Duration>>,
+        buffer: Vec<u8>,
+        position: usize,
+    }
+
+    impl FrameReader {
+        /// Blocks until the stream has data to read, or fails if the deadline passes first.
+        fn wait(&self, deadline: Option<Instant>) -> io::Result<()> {
+            loop {
+                match self.stream.peek(&mut [0]) {
+                    Ok(_) => return Ok(()),
+                    Err(err) if is_poll_timeout(&err) => {}
+                    Err(err) => return Err(err),
+                }
+                if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                    return Err(io::ErrorKind::TimedOut.into());
+                }
+            }
+        }
+    }
+
+    impl Read for FrameReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let deadline = self.timeout.get().map(|timeout| Instant::now() + timeout);
+            while self.position == self.buffer.len() {
+                // Reading also sends any queued replies to pings.
+                let mut socket = lock(&self.socket);
+                let data = match socket.read() {
+                    Ok(Message::Text(text)) => text.into_bytes(),
+                    Ok(Message::Binary(data)) => data,
+                    Ok(Message::Close(_)) => {
+                        // Send the reply to the close frame, which tungstenite has queued.
+                        _ = socket.flush();
+                        return Ok(0);
+                    }
+                    Err(
+                        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed,
+                    ) => return Ok(0),
+                    Ok(_) => continue,
+                    Err(tungstenite::Error::Io(err)) if is_poll_timeout(&err) => {
+                        drop(socket);
+                        self.wait(deadline)?;
+                        continue;
+                    }
+                    Err(tungstenite::Error::Io(err)) => return Err(err),
+                    Err(err) => return Err(io::Error::other(err)),
+                };
+                self.buffer = data;
+                if self.buffer.last() != Some(&b'\n') {
+                    self.buffer.push(b'\n');
+                }
+                self.position = 0;
+            }
+            let len = buf.len().min(self.buffer.len() - self.position);
+            buf[..len].copy_from_slice(&self.buffer[self.position..self.position + len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    impl ReadTimeout for FrameReader {
+        fn read_timeout(&self) -> io::Result<Option<Duration>> {
+            Ok(self.timeout.get())
+        }
+
+        fn set_read_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+            self.timeout.set(timeout);
+            Ok(())
+        }
+    }
+
+    /// Sends each complete line written to it as a text frame when flushed.
+    struct FrameWriter {
+        socket: Arc<Mutex<WebSocket<TcpStream>>>,
+        pending: Vec<u8>,
+    }
+
+    impl Write for FrameWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.pending.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            let Some(end) = self.pending.iter().rposition(|&b| b == b'\n') else {
+                return Ok(());
+            };
+            let lines: Vec<u8> = self.pending.drain(..=end).collect();
+            let mut socket = lock(&self.socket);
+            for line in lines.split(|&b| b == b'\n').filter(|line| !line.is_empty()) {
+                let text = String::from_utf8(line.to_vec())
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                socket
+                    .write(Message::Text(text))
+                    .map_err(io::Error::other)?;
+            }
+            socket.flush().map_err(io::Error::other)
+        }
+    }
+}
@@ -0,0 +1,662 @@
+//! Messages for version 1 of the protocol.
+//!
+//! Version 2 changed how [`DrawCommand::Write`] describes the background behind text and how
+//! [`DeviceStatus::Motor`] describes its direction. Those two types are versioned here, along
+//! with an [`Event`] that uses them and conversions to and from the current types at the crate
+//! root. Conversions that discard information say so.
+//!
+//! Every other payload, and every [`Command`], is shared with the current version, so it is
+//! sent to version 1 peers in its current form. Fields added to those types since version 1
+//! have defaults, so messages from version 1 peers still parse and version 1 peers can ignore
+//! the new fields. Variants added since version 1 are passed through as-is, and version 1 peers
+//! will not recognize them. Fields are documented on the current types.
+
+use std::num::NonZeroU16;
+
+use mint::Point2;
+use serde::{Deserialize, Serialize};
+
+pub use crate::Command;
+use crate::{
+    AutonRoutine, Battery, Color, ConfigureDevicesResult, ControllerId, ExtraDisplay, Fidelity,
+    FrameStreamConfig, GpsMount, HandshakeRejection, LedState, LegalityReport, LinkMode, LogLevel,
+    LogSource, MotorBrakeMode, MotorDirection, MotorGearset, MotorLimits, MotorSample,
+    Notification, Port, PortSerialData, ProgramSlot, Rect, RobotState, RunSummary, Screenshot,
+    ScreenshotTrigger, ScrollLocation, SerialData, Shape, SmartPort, SourceLocation, StatusDelta,
+    TextBackground, TextLocation, TouchListening, V5Text, VCodeSig, WatchdogTimeout,
+};
+
+/// The version number sent in [`Event::Handshake`] and [`Command::Handshake`] by
+/// implementations of this protocol version.
+pub const VERSION: i32 = 1;
+
+/// A message sent from the simulator to the frontend.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum Event {
+    #[serde(rename = "Handshake")]
+    Handshake {
+        version: i32,
+        extensions: Vec<String>,
+    },
+    #[serde(rename = "ScreenDraw")]
+    ScreenDraw {
+        command: DrawCommand,
+        color: Color,
+        clip_region: Rect,
+    },
+    #[serde(rename = "ScreenScroll")]
+    ScreenScroll {
+        location: ScrollLocation,
+        lines: i32,
+        background: Color,
+        clip_region: Rect,
+    },
+    #[serde(rename = "ScreenClear")]
+    ScreenClear { color: Color, clip_region: Rect },
+    #[serde(rename = "ScreenDoubleBufferMode")]
+    ScreenDoubleBufferMode { enable: bool },
+    #[serde(rename = "ScreenRender")]
+    ScreenRender,
+    #[serde(rename = "VCodeSig")]
+    VCodeSig(VCodeSig),
+    #[serde(rename = "Ready")]
+    Ready,
+    #[serde(rename = "Exited")]
+    Exited,
+    #[serde(rename = "Serial")]
+    Serial(SerialData),
+    #[serde(rename = "DeviceUpdate")]
+    DeviceUpdate { status: DeviceStatus, port: Port },
+    #[serde(rename = "DeviceLed")]
+    DeviceLed { port: SmartPort, led: LedState },
+    #[serde(rename = "Battery")]
+    Battery(Battery),
+    #[serde(rename = "RobotPose")]
+    RobotPose { x: f64, y: f64 },
+    #[serde(rename = "RobotState")]
+    RobotState(RobotState),
+    #[serde(rename = "Log")]
+    Log {
+        level: LogLevel,
+        message: String,
+        #[serde(default)]
+        source: LogSource,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        color: Option<Color>,
+        #[serde(default)]
+        location: Option<SourceLocation>,
+        #[serde(default = "crate::one")]
+        count: u32,
+    },
+    #[serde(rename = "VEXLinkConnect")]
+    VEXLinkConnect {
+        port: SmartPort,
+        id: String,
+        mode: LinkMode,
+        r#override: bool,
+    },
+    #[serde(rename = "VEXLinkDisconnect")]
+    VEXLinkDisconnect { port: SmartPort },
+    #[serde(rename = "TextMetricsRequest")]
+    TextMetricsRequest { text: V5Text },
+    #[serde(rename = "SelectController")]
+    SelectController {
+        id: ControllerId,
+        uuid: Option<String>,
+    },
+    #[serde(rename = "GpsMount")]
+    GpsMount { port: SmartPort, mount: GpsMount },
+    #[serde(rename = "VisionStream")]
+    VisionStream {
+        port: SmartPort,
+        config: Option<FrameStreamConfig>,
+    },
+    #[serde(rename = "WatchdogExpired")]
+    WatchdogExpired { timeout: WatchdogTimeout },
+    #[serde(rename = "LegalityReport")]
+    LegalityReport(LegalityReport),
+    #[serde(rename = "Screenshot")]
+    Screenshot {
+        trigger: ScreenshotTrigger,
+        screenshot: Screenshot,
+    },
+    #[serde(rename = "DevicesConfigured")]
+    DevicesConfigured(ConfigureDevicesResult),
+    #[serde(rename = "RunSummary")]
+    RunSummary(RunSummary),
+    #[serde(rename = "ProgramSlots")]
+    ProgramSlots(Vec<ProgramSlot>),
+    #[serde(rename = "Fidelity")]
+    Fidelity(Fidelity),
+    #[serde(rename = "Sync")]
+    Sync { seq: u64 },
+    #[serde(rename = "Notification")]
+    Notification(Notification),
+    #[serde(rename = "AutonRoutines")]
+    AutonRoutines(Vec<AutonRoutine>),
+    #[serde(rename = "AdiOutput")]
+    AdiOutput { port: Port, high: bool },
+    #[serde(rename = "DeviceDelta")]
+    DeviceDelta { port: Port, delta: StatusDelta },
+    #[serde(rename = "AdiLedBuffer")]
+    AdiLedBuffer { port: Port, colors: Vec<Color> },
+    #[serde(rename = "TouchListening")]
+    TouchListening(TouchListening),
+    #[serde(rename = "AdiPwmOutput")]
+    AdiPwmOutput { port: Port, value: i8 },
+    #[serde(rename = "PortClaimed")]
+    PortClaimed { port: Port, kind: String },
+    #[serde(rename = "AdiAnalogOutput")]
+    AdiAnalogOutput { port: Port, voltage: f64 },
+    #[serde(rename = "DisplayOpened")]
+    DisplayOpened(ExtraDisplay),
+    #[serde(rename = "DisplayClosed")]
+    DisplayClosed { display: u32 },
+    #[serde(rename = "DisplayDraw")]
+    DisplayDraw {
+        display: u32,
+        command: DrawCommand,
+        color: Color,
+        clip_region: Rect,
+    },
+    #[serde(rename = "DisplayClear")]
+    DisplayClear {
+        display: u32,
+        color: Color,
+        clip_region: Rect,
+    },
+    #[serde(rename = "DisplayRender")]
+    DisplayRender { display: u32 },
+    #[serde(rename = "PortSerial")]
+    PortSerial(PortSerialData),
+    #[serde(rename = "HandshakeRejected")]
+    HandshakeRejected(HandshakeRejection),
+    #[serde(rename = "MotorLimits")]
+    MotorLimits {
+        port: SmartPort,
+        limits: MotorLimits,
+    },
+    #[serde(rename = "MotorTelemetry")]
+    MotorTelemetry {
+        port: SmartPort,
+        samples: Vec<MotorSample>,
+    },
+}
+
+/// An instruction for drawing to the robot LCD screen.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DrawCommand {
+    #[serde(rename = "Fill")]
+    Fill { shape: Shape },
+    #[serde(rename = "Stroke")]
+    Stroke { shape: Shape },
+    #[serde(rename = "CopyBuffer")]
+    CopyBuffer {
+        top_left: Point2<i32>,
+        bottom_right: Point2<i32>,
+        stride: NonZeroU16,
+        buffer: String,
+    },
+    /// Text drawn over the existing pixels if `opaque` is false, or over a box filled with
+    /// `background` if it is true.
+    #[serde(rename = "Write")]
+    Write {
+        text: V5Text,
+        location: TextLocation,
+        opaque: bool,
+        background: Color,
+    },
+}
+
+/// The current state of a V5 peripheral.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum DeviceStatus {
+    /// A motor's status, with only the fields version 1 defined.
+    ///
+    /// `reversed` is the motor's configured direction.
+    #[serde(rename = "Motor")]
+    Motor {
+        velocity: f64,
+        reversed: bool,
+        power_draw: f64,
+        torque_output: f64,
+        flags: i32,
+        position: f64,
+        target_position: f64,
+        voltage: f64,
+        gearset: MotorGearset,
+        brake_mode: MotorBrakeMode,
+    },
+    /// The status of a device added after version 1, which is sent in its current form.
+    #[serde(untagged)]
+    Current(crate::DeviceStatus),
+}
+
+impl From<crate::DeviceStatus> for DeviceStatus {
+    /// Converts a current device status to version 1. Motor fields added after version 1
+    /// are discarded.
+    fn from(status: crate::DeviceStatus) -> Self {
+        match status {
+            crate::DeviceStatus::Motor {
+                velocity,
+                direction_configured,
+                power_draw,
+                torque_output,
+                flags,
+                position,
+                target_position,
+                voltage,
+                gearset,
+                brake_mode,
+                ..
+            } => Self::Motor {
+                velocity,
+                reversed: direction_configured.is_reverse(),
+                power_draw,
+                torque_output,
+                flags,
+                position,
+                target_position,
+                voltage,
+                gearset,
+                brake_mode,
+            },
+            status => Self::Current(status),
+        }
+    }
+}
+
+impl From<DeviceStatus> for crate::DeviceStatus {
+    /// Converts a version 1 device status to the current version. Motor fields added after
+    /// version 1 are set to their defaults.
+    fn from(status: DeviceStatus) -> Self {
+        match status {
+            DeviceStatus::Motor {
+                velocity,
+                reversed,
+                power_draw,
+                torque_output,
+                flags,
+                position,
+                target_position,
+                voltage,
+                gearset,
+                brake_mode,
+            } => Self::Motor {
+                velocity,
+                direction_configured: if reversed {
+                    MotorDirection::Reverse
+                } else {
+                    MotorDirection::Forward
+                },
+                spinning_reverse: false,
+                power_draw,
+                torque_output,
+                flags,
+                position,
+                target_position,
+                voltage,
+                gearset,
+                brake_mode,
+                encoder: None,
+                temperature: 0.0,
+                current: 0.0,
+                efficiency: 0.0,
+                faults: Default::default(),
+            },
+            DeviceStatus::Current(status) => status,
+        }
+    }
+}
+
+impl From<crate::DrawCommand> for DrawCommand {
+    /// Converts a current draw command to version 1. Transparent text is sent with the
+    /// default background color, which version 1 implementations ignore.
+    fn from(command: crate::DrawCommand) -> Self {
+        match command {
+            crate::DrawCommand::Fill { shape } => Self::Fill { shape },
+            crate::DrawCommand::Stroke { shape } => Self::Stroke { shape },
+            crate::DrawCommand::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            } => Self::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            },
+            crate::DrawCommand::Write {
+                text,
+                location,
+                background,
+            } => {
+                let (opaque, background) = match background {
+                    TextBackground::Transparent => (false, Color::default()),
+                    TextBackground::Solid(color) => (true, color),
+                };
+                Self::Write {
+                    text,
+                    location,
+                    opaque,
+                    background,
+                }
+            }
+        }
+    }
+}
+
+impl From<DrawCommand> for crate::DrawCommand {
+    /// Converts a version 1 draw command to the current version. The background color of
+    /// text that is not `opaque` is discarded.
+    fn from(command: DrawCommand) -> Self {
+        match command {
+            DrawCommand::Fill { shape } => Self::Fill { shape },
+            DrawCommand::Stroke { shape } => Self::Stroke { shape },
+            DrawCommand::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            } => Self::CopyBuffer {
+                top_left,
+                bottom_right,
+                stride,
+                buffer,
+            },
+            DrawCommand::Write {
+                text,
+                location,
+                opaque,
+                background,
+            } => Self::Write {
+                text,
+                location,
+                background: if opaque {
+                    TextBackground::Solid(background)
+                } else {
+                    TextBackground::Transparent
+                },
+            },
+        }
+    }
+}
+
+impl From<crate::Event> for Event {
+    /// Converts a current event to version 1, as described by [`DrawCommand`]'s and
+    /// [`DeviceStatus`]'s conversions.
+    fn from(event: crate::Event) -> Self {
+        match event {
+            crate::Event::Handshake {
+                version,
+                extensions,
+            } => Self::Handshake {
+                version,
+                extensions,
+            },
+            crate::Event::ScreenDraw {
+                command,
+                color,
+                clip_region,
+            } => Self::ScreenDraw {
+                command: command.into(),
+                color,
+                clip_region,
+            },
+            crate::Event::ScreenScroll {
+                location,
+                lines,
+                background,
+                clip_region,
+            } => Self::ScreenScroll {
+                location,
+                lines,
+                background,
+                clip_region,
+            },
+            crate::Event::ScreenClear { color, clip_region } => {
+                Self::ScreenClear { color, clip_region }
+            }
+            crate::Event::ScreenDoubleBufferMode { enable } => {
+                Self::ScreenDoubleBufferMode { enable }
+            }
+            crate::Event::ScreenRender => Self::ScreenRender,
+            crate::Event::VCodeSig(value) => Self::VCodeSig(value),
+            crate::Event::Ready => Self::Ready,
+            crate::Event::Exited => Self::Exited,
+            crate::Event::Serial(value) => Self::Serial(value),
+            crate::Event::DeviceUpdate { status, port } => Self::DeviceUpdate {
+                status: status.into(),
+                port,
+            },
+            crate::Event::DeviceLed { port, led } => Self::DeviceLed { port, led },
+            crate::Event::Battery(value) => Self::Battery(value),
+            crate::Event::RobotPose { x, y } => Self::RobotPose { x, y },
+            crate::Event::RobotState(value) => Self::RobotState(value),
+            crate::Event::Log {
+                level,
+                message,
+                source,
+                category,
+                color,
+                location,
+                count,
+            } => Self::Log {
+                level,
+                message,
+                source,
+                category,
+                color,
+                location,
+                count,
+            },
+            crate::Event::VEXLinkConnect {
+                port,
+                id,
+                mode,
+                r#override,
+            } => Self::VEXLinkConnect {
+                port,
+                id,
+                mode,
+                r#override,
+            },
+            crate::Event::VEXLinkDisconnect { port } => Self::VEXLinkDisconnect { port },
+            crate::Event::TextMetricsRequest { text } => Self::TextMetricsRequest { text },
+            crate::Event::SelectController { id, uuid } => Self::SelectController { id, uuid },
+            crate::Event::GpsMount { port, mount } => Self::GpsMount { port, mount },
+            crate::Event::VisionStream { port, config } => Self::VisionStream { port, config },
+            crate::Event::WatchdogExpired { timeout } => Self::WatchdogExpired { timeout },
+            crate::Event::LegalityReport(value) => Self::LegalityReport(value),
+            crate::Event::Screenshot {
+                trigger,
+                screenshot,
+            } => Self::Screenshot {
+                trigger,
+                screenshot,
+            },
+            crate::Event::DevicesConfigured(value) => Self::DevicesConfigured(value),
+            crate::Event::RunSummary(value) => Self::RunSummary(value),
+            crate::Event::ProgramSlots(value) => Self::ProgramSlots(value),
+            crate::Event::Fidelity(value) => Self::Fidelity(value),
+            crate::Event::Sync { seq } => Self::Sync { seq },
+            crate::Event::Notification(value) => Self::Notification(value),
+            crate::Event::AutonRoutines(value) => Self::AutonRoutines(value),
+            crate::Event::AdiOutput { port, high } => Self::AdiOutput { port, high },
+            crate::Event::DeviceDelta { port, delta } => Self::DeviceDelta { port, delta },
+            crate::Event::AdiLedBuffer { port, colors } => Self::AdiLedBuffer { port, colors },
+            crate::Event::TouchListening(value) => Self::TouchListening(value),
+            crate::Event::AdiPwmOutput { port, value } => Self::AdiPwmOutput { port, value },
+            crate::Event::PortClaimed { port, kind } => Self::PortClaimed { port, kind },
+            crate::Event::AdiAnalogOutput { port, voltage } => {
+                Self::AdiAnalogOutput { port, voltage }
+            }
+            crate::Event::DisplayOpened(value) => Self::DisplayOpened(value),
+            crate::Event::DisplayClosed { display } => Self::DisplayClosed { display },
+            crate::Event::DisplayDraw {
+                display,
+                command,
+                color,
+                clip_region,
+            } => Self::DisplayDraw {
+                display,
+                command: command.into(),
+                color,
+                clip_region,
+            },
+            crate::Event::DisplayClear {
+                display,
+                color,
+                clip_region,
+            } => Self::DisplayClear {
+                display,
+                color,
+                clip_region,
+            },
+            crate::Event::DisplayRender { display } => Self::DisplayRender { display },
+            crate::Event::PortSerial(value) => Self::PortSerial(value),
+            crate::Event::HandshakeRejected(value) => Self::HandshakeRejected(value),
+            crate::Event::MotorLimits { port, limits } => Self::MotorLimits { port, limits },
+            crate::Event::MotorTelemetry { port, samples } => {
+                Self::MotorTelemetry { port, samples }
+            }
+        }
+    }
+}
+
+impl From<Event> for crate::Event {
+    /// Converts a version 1 event to the current version, as described by [`DrawCommand`]'s and
+    /// [`DeviceStatus`]'s conversions.
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Handshake {
+                version,
+                extensions,
+            } => Self::Handshake {
+                version,
+                extensions,
+            },
+            Event::ScreenDraw {
+                command,
+                color,
+                clip_region,
+            } => Self::ScreenDraw {
+                command: command.into(),
+                color,
+                clip_region,
+            },
+            Event::ScreenScroll {
+                location,
+                lines,
+                background,
+                clip_region,
+            } => Self::ScreenScroll {
+                location,
+                lines,
+                background,
+                clip_region,
+            },
+            Event::ScreenClear { color, clip_region } => Self::ScreenClear { color, clip_region },
+            Event::ScreenDoubleBufferMode { enable } => Self::ScreenDoubleBufferMode { enable },
+            Event::ScreenRender => Self::ScreenRender,
+            Event::VCodeSig(value) => Self::VCodeSig(value),
+            Event::Ready => Self::Ready,
+            Event::Exited => Self::Exited,
+            Event::Serial(value) => Self::Serial(value),
+            Event::DeviceUpdate { status, port } => Self::DeviceUpdate {
+                status: status.into(),
+                port,
+            },
+            Event::DeviceLed { port, led } => Self::DeviceLed { port, led },
+            Event::Battery(value) => Self::Battery(value),
+            Event::RobotPose { x, y } => Self::RobotPose { x, y },
+            Event::RobotState(value) => Self::RobotState(value),
+            Event::Log {
+                level,
+                message,
+                source,
+                category,
+                color,
+                location,
+                count,
+            } => Self::Log {
+                level,
+                message,
+                source,
+                category,
+                color,
+                location,
+                count,
+            },
+            Event::VEXLinkConnect {
+                port,
+                id,
+                mode,
+                r#override,
+            } => Self::VEXLinkConnect {
+                port,
+                id,
+                mode,
+                r#override,
+            },
+            Event::VEXLinkDisconnect { port } => Self::VEXLinkDisconnect { port },
+            Event::TextMetricsRequest { text } => Self::TextMetricsRequest { text },
+            Event::SelectController { id, uuid } => Self::SelectController { id, uuid },
+            Event::GpsMount { port, mount } => Self::GpsMount { port, mount },
+            Event::VisionStream { port, config } => Self::VisionStream { port, config },
+            Event::WatchdogExpired { timeout } => Self::WatchdogExpired { timeout },
+            Event::LegalityReport(value) => Self::LegalityReport(value),
+            Event::Screenshot {
+                trigger,
+                screenshot,
+            } => Self::Screenshot {
+                trigger,
+                screenshot,
+            },
+            Event::DevicesConfigured(value) => Self::DevicesConfigured(value),
+            Event::RunSummary(value) => Self::RunSummary(value),
+            Event::ProgramSlots(value) => Self::ProgramSlots(value),
+            Event::Fidelity(value) => Self::Fidelity(value),
+            Event::Sync { seq } => Self::Sync { seq },
+            Event::Notification(value) => Self::Notification(value),
+            Event::AutonRoutines(value) => Self::AutonRoutines(value),
+            Event::AdiOutput { port, high } => Self::AdiOutput { port, high },
+            Event::DeviceDelta { port, delta } => Self::DeviceDelta { port, delta },
+            Event::AdiLedBuffer { port, colors } => Self::AdiLedBuffer { port, colors },
+            Event::TouchListening(value) => Self::TouchListening(value),
+            Event::AdiPwmOutput { port, value } => Self::AdiPwmOutput { port, value },
+            Event::PortClaimed { port, kind } => Self::PortClaimed { port, kind },
+            Event::AdiAnalogOutput { port, voltage } => Self::AdiAnalogOutput { port, voltage },
+            Event::DisplayOpened(value) => Self::DisplayOpened(value),
+            Event::DisplayClosed { display } => Self::DisplayClosed { display },
+            Event::DisplayDraw {
+                display,
+                command,
+                color,
+                clip_region,
+            } => Self::DisplayDraw {
+                display,
+                command: command.into(),
+                color,
+                clip_region,
+            },
+            Event::DisplayClear {
+                display,
+                color,
+                clip_region,
+            } => Self::DisplayClear {
+                display,
+                color,
+                clip_region,
+            },
+            Event::DisplayRender { display } => Self::DisplayRender { display },
+            Event::PortSerial(value) => Self::PortSerial(value),
+            Event::HandshakeRejected(value) => Self::HandshakeRejected(value),
+            Event::MotorLimits { port, limits } => Self::MotorLimits { port, limits },
+            Event::MotorTelemetry { port, samples } => Self::MotorTelemetry { port, samples },
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Messages for version 2 of the protocol.
+//!
+//! Version 2 is the current protocol version, so its messages are the same as the types at the
+//! crate root. When a breaking change introduces a new version, the types it changes will be
+//! versioned here like those in [`v1`](crate::v1).
+
+pub use crate::{Command, Event};
+
+/// The version number sent in [`Event::Handshake`] and [`Command::Handshake`] by
+/// implementations of this protocol version.
+pub const VERSION: i32 = 2;
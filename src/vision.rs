@@ -0,0 +1,138 @@
+//! Vision sensor input.
+
+use base64::{prelude::*, DecodeError};
+use mint::{Point2, Quaternion, Vector3};
+use serde::{Deserialize, Serialize};
+
+/// The resolution and frame rate of a camera frame stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FrameStreamConfig {
+    pub width: u16,
+    pub height: u16,
+    /// The number of frames to send each second.
+    pub fps: u16,
+    pub format: FrameFormat,
+}
+
+/// The encoding of a camera frame's pixel data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum FrameFormat {
+    /// Uncompressed 8-bit RGB pixels in row-major order.
+    #[serde(rename = "Rgb8")]
+    Rgb8,
+    #[serde(rename = "Png")]
+    Png,
+    #[serde(rename = "Jpeg")]
+    Jpeg,
+}
+
+/// A single camera frame sent to a vision sensor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct VisionFrame {
+    pub width: u16,
+    pub height: u16,
+    pub format: FrameFormat,
+    /// Base64-encoded pixel data.
+    pub data: String,
+}
+
+impl VisionFrame {
+    pub fn new(width: u16, height: u16, format: FrameFormat, bytes: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            format,
+            data: BASE64_STANDARD.encode(bytes),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DecodeError> {
+        BASE64_STANDARD.decode(&self.data)
+    }
+}
+
+/// An object detected by a vision sensor.
+///
+/// Coordinates are in image pixels, with the origin at the top-left corner of the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct VisionObject {
+    /// The color signature that matched the object, from 1 to 7.
+    pub signature: u8,
+    /// The top-left corner of the object's bounding box.
+    pub top_left: Point2<i32>,
+    pub width: u16,
+    pub height: u16,
+    pub center: Point2<i32>,
+}
+
+/// A family of AprilTag fiducial markers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[non_exhaustive]
+pub enum AprilTagFamily {
+    #[serde(rename = "Tag16h5")]
+    Tag16h5,
+    #[serde(rename = "Tag21h7")]
+    Tag21h7,
+    #[serde(rename = "Tag25h9")]
+    Tag25h9,
+    #[serde(rename = "Tag36h11")]
+    Tag36h11,
+}
+
+/// An AprilTag seen by a camera.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct AprilTagDetection {
+    pub family: AprilTagFamily,
+    pub id: u32,
+    /// The corners of the tag in image pixel coordinates, counterclockwise from the bottom left
+    /// corner of the tag.
+    pub corners: [Point2<f64>; 4],
+    /// The center of the tag in image pixel coordinates.
+    pub center: Point2<f64>,
+    /// The estimated pose of the tag relative to the camera, if the tag's size is known.
+    pub pose: Option<TagPose>,
+}
+
+/// The position and orientation of a tag relative to the camera that detected it.
+///
+/// The camera frame has +x to the right of the image, +y down, and +z out of the lens.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct TagPose {
+    /// The tag's center, in meters.
+    pub translation: Vector3<f64>,
+    pub rotation: Quaternion<f64>,
+}
+
+/// A region matching one of an AI Vision sensor's color signatures.
+///
+/// Coordinates are in image pixels, with the origin at the top-left corner of the image.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ColorBlob {
+    /// The color signature that matched the blob, from 1 to 7.
+    pub signature: u8,
+    /// The top-left corner of the blob's bounding box.
+    pub top_left: Point2<i32>,
+    pub width: u16,
+    pub height: u16,
+    pub center: Point2<i32>,
+    /// The angle of the blob's major axis, in degrees clockwise.
+    pub angle: f64,
+}
+
+/// An object recognized by an AI Vision sensor's detection model.
+///
+/// Coordinates are in image pixels, with the origin at the top-left corner of the image.
+#[derive(Debug, Clone, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct ModelDetection {
+    /// The index of the object's class in the model.
+    pub class_id: u32,
+    /// The name of the object's class, such as `"Red Ring"`.
+    pub label: String,
+    /// How confident the model is in the detection, from 0.0 to 1.0.
+    pub confidence: f64,
+    /// The top-left corner of the object's bounding box.
+    pub top_left: Point2<i32>,
+    pub width: u16,
+    pub height: u16,
+}
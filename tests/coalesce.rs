@@ -0,0 +1,57 @@
+use vexide_simulator_protocol::{coalesce::collapse_logs, Event, LogLevel};
+
+fn log(message: &str, count: u32) -> Event {
+    let mut event = Event::log(LogLevel::Info, message);
+    if let Event::Log { count: total, .. } = &mut event {
+        *total = count;
+    }
+    event
+}
+
+fn pose(x: f64) -> Event {
+    Event::RobotPose { x, y: 0.0 }
+}
+
+#[test]
+fn merges_adjacent_repetitions() {
+    assert_eq!(
+        collapse_logs([log("a", 1), log("a", 1), log("a", 3)]),
+        [log("a", 5)]
+    );
+}
+
+#[test]
+fn merges_across_other_events_at_first_position() {
+    assert_eq!(
+        collapse_logs([log("a", 1), pose(1.0), log("a", 1), pose(2.0), log("a", 1)]),
+        [log("a", 3), pose(1.0), pose(2.0)]
+    );
+}
+
+#[test]
+fn other_log_messages_end_the_run() {
+    assert_eq!(
+        collapse_logs([log("a", 1), log("b", 1), log("a", 1), log("a", 1)]),
+        [log("a", 1), log("b", 1), log("a", 2)]
+    );
+}
+
+#[test]
+fn messages_differing_in_any_field_are_kept() {
+    let mut warning = log("a", 1);
+    if let Event::Log { level, .. } = &mut warning {
+        *level = LogLevel::Warn;
+    }
+    assert_eq!(
+        collapse_logs([log("a", 1), warning.clone()]),
+        [log("a", 1), warning]
+    );
+}
+
+#[test]
+fn count_saturates() {
+    assert_eq!(
+        collapse_logs([log("a", u32::MAX), log("a", 1)]),
+        [log("a", u32::MAX)]
+    );
+}
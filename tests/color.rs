@@ -0,0 +1,42 @@
+use serde_json::json;
+use vexide_simulator_protocol::Color;
+
+#[test]
+fn serializes_as_integer() {
+    assert_eq!(
+        serde_json::to_value(Color(0x12AB34)).unwrap(),
+        json!(0x12AB34)
+    );
+}
+
+#[test]
+fn deserializes_from_either_form() {
+    for value in [json!(0x12AB34), json!("#12AB34"), json!("#12ab34")] {
+        assert_eq!(
+            serde_json::from_value::<Color>(value.clone()).unwrap(),
+            Color(0x12AB34),
+            "{value}"
+        );
+    }
+}
+
+#[test]
+fn rejects_malformed_strings() {
+    for value in ["12AB34", "#12AB3", "#12AB345", "#12AB3G", "#+2AB34", ""] {
+        assert!(
+            serde_json::from_value::<Color>(json!(value)).is_err(),
+            "{value}"
+        );
+        assert!(Color::from_hex(value).is_err(), "{value}");
+    }
+    assert!(serde_json::from_value::<Color>(json!(u64::from(u32::MAX) + 1)).is_err());
+}
+
+#[test]
+fn hex_round_trips() {
+    assert_eq!(Color(0x00FF00).to_hex(), "#00FF00");
+    assert_eq!(
+        Color::from_hex(&Color(0xABCDEF).to_hex()),
+        Ok(Color(0xABCDEF))
+    );
+}
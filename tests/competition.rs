@@ -0,0 +1,172 @@
+use vexide_simulator_protocol::{
+    recording::{Entry, Message, Recording, RecordingHeader},
+    state::RobotStateTracker,
+    Command, CompMode, CompetitionMode, CompetitionPhase,
+};
+
+fn mode(enabled: bool, mode: CompMode) -> CompetitionMode {
+    CompetitionMode {
+        enabled,
+        connected: true,
+        mode,
+        is_competition: true,
+    }
+}
+
+fn auton() -> CompetitionMode {
+    mode(true, CompMode::Auto)
+}
+
+fn driver() -> CompetitionMode {
+    mode(true, CompMode::Driver)
+}
+
+/// 15 seconds of autonomous, a 2 second gap, and 105 seconds of driver control.
+fn schedule() -> Vec<CompetitionPhase> {
+    CompetitionPhase::match_schedule(15_000, 2_000, 105_000)
+}
+
+#[test]
+fn mode_at_follows_phases() {
+    let schedule = schedule();
+    assert_eq!(CompetitionPhase::mode_at(&schedule, 0), Some(auton()));
+    assert_eq!(CompetitionPhase::mode_at(&schedule, 14_999), Some(auton()));
+    assert_eq!(
+        CompetitionPhase::mode_at(&schedule, 15_000),
+        Some(mode(false, CompMode::Auto))
+    );
+    assert_eq!(CompetitionPhase::mode_at(&schedule, 17_000), Some(driver()));
+    assert_eq!(
+        CompetitionPhase::mode_at(&schedule, 122_000),
+        Some(mode(false, CompMode::Driver))
+    );
+    assert_eq!(
+        CompetitionPhase::mode_at(&schedule, u64::MAX),
+        Some(mode(false, CompMode::Driver))
+    );
+    assert_eq!(CompetitionPhase::mode_at(&[], 0), None);
+}
+
+#[test]
+fn starts_skip_empty_phases() {
+    let mut schedule = schedule();
+    schedule.insert(
+        1,
+        CompetitionPhase {
+            mode: driver(),
+            duration_ms: 0,
+        },
+    );
+    assert_eq!(
+        CompetitionPhase::starts(&schedule),
+        [
+            (0, auton()),
+            (15_000, mode(false, CompMode::Auto)),
+            (17_000, driver()),
+            (122_000, mode(false, CompMode::Driver)),
+        ]
+    );
+    assert_eq!(CompetitionPhase::starts(&[]), []);
+}
+
+#[test]
+fn tracker_runs_schedule() {
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_command(&Command::CompetitionSchedule(schedule()));
+    assert_eq!(tracker.competition_mode(), &auton());
+    tracker.advance(16_000);
+    assert_eq!(tracker.competition_mode(), &mode(false, CompMode::Auto));
+    tracker.advance(1_000);
+    assert_eq!(tracker.competition_mode(), &driver());
+    tracker.advance(200_000);
+    assert_eq!(tracker.competition_mode(), &mode(false, CompMode::Driver));
+}
+
+#[test]
+fn competition_mode_cancels_schedule() {
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_command(&Command::CompetitionSchedule(schedule()));
+    tracker.apply_command(&Command::CompetitionMode(driver()));
+    tracker.advance(200_000);
+    assert_eq!(tracker.competition_mode(), &driver());
+}
+
+#[test]
+fn empty_schedule_keeps_mode() {
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_command(&Command::CompetitionMode(driver()));
+    tracker.apply_command(&Command::CompetitionSchedule(Vec::new()));
+    tracker.advance(1_000);
+    assert_eq!(tracker.competition_mode(), &driver());
+}
+
+fn recording() -> Recording {
+    let mut recording = Recording::new(RecordingHeader::new(2, 0));
+    recording.entries.push(Entry {
+        time_ms: 1_000,
+        message: Message::Command(Command::CompetitionSchedule(schedule())),
+    });
+    recording.entries.push(Entry {
+        time_ms: 30_000,
+        message: Message::Command(Command::StopProgram),
+    });
+    recording
+}
+
+#[test]
+fn seek_follows_schedule() {
+    let recording = recording();
+    assert_eq!(
+        recording.seek_to(0).robot.competition_mode(),
+        &CompetitionMode::default()
+    );
+    assert_eq!(recording.seek_to(1_000).robot.competition_mode(), &auton());
+    assert_eq!(
+        recording.seek_to(16_500).robot.competition_mode(),
+        &mode(false, CompMode::Auto)
+    );
+    assert_eq!(
+        recording.seek_to(18_000).robot.competition_mode(),
+        &driver()
+    );
+    assert_eq!(
+        recording.seek_to(60_000).robot.competition_mode(),
+        &driver()
+    );
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn chrome_trace_shows_scheduled_phases() {
+    use serde_json::Value;
+    use vexide_simulator_protocol::export;
+
+    let mut trace = Vec::new();
+    export::write_chrome_trace(&recording(), &mut trace).unwrap();
+    let trace: Value = serde_json::from_slice(&trace).unwrap();
+    let spans: Vec<_> = trace["traceEvents"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|event| event["tid"] == 2 && event["ph"] != "M")
+        .map(|event| {
+            (
+                event["ph"].as_str().unwrap(),
+                event["name"].as_str().unwrap(),
+                event["ts"].as_u64().unwrap() / 1000,
+            )
+        })
+        .collect();
+    assert_eq!(
+        spans,
+        [
+            ("B", "Autonomous", 1_000),
+            ("E", "Autonomous", 16_000),
+            ("B", "Disabled", 16_000),
+            ("E", "Disabled", 18_000),
+            ("B", "Driver", 18_000),
+            // The recording ends before the match does.
+            ("E", "Driver", 30_000),
+        ]
+    );
+}
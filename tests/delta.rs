@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+
+use mint::{Quaternion, Vector3};
+use vexide_simulator_protocol::{
+    delta::{self, DeltaEncoder},
+    state::RobotStateTracker,
+    DeviceStatus, EulerAngles, Event, FieldValue, Port, SmartPort, StatusDelta,
+};
+
+const PORT: Port = Port::Smart(SmartPort(1));
+
+fn imu(heading: f64) -> DeviceStatus {
+    DeviceStatus::Imu {
+        heading,
+        rotation: heading,
+        quaternion: Quaternion {
+            v: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            s: 1.0,
+        },
+        euler: EulerAngles {
+            pitch: 0.0,
+            roll: 0.0,
+            yaw: heading,
+        },
+        gyro_rate: Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.5,
+        },
+        acceleration: Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 9.8,
+        },
+        calibrating: false,
+    }
+}
+
+fn distance(distance_mm: Option<u32>) -> DeviceStatus {
+    DeviceStatus::Distance {
+        distance_mm,
+        confidence: 63,
+        object_size: 200,
+        object_velocity: 0.0,
+    }
+}
+
+fn delta<const N: usize>(fields: [(&str, FieldValue); N]) -> StatusDelta {
+    StatusDelta(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+#[test]
+fn diff_then_apply_round_trips() {
+    for (old, new) in [
+        (imu(0.0), imu(90.0)),
+        (distance(None), distance(Some(350))),
+        (distance(Some(350)), distance(None)),
+    ] {
+        let delta = delta::diff(&old, &new).unwrap();
+        assert_eq!(delta::apply(&old, &delta).unwrap(), new);
+    }
+}
+
+#[test]
+fn diff_contains_only_changed_fields() {
+    let delta = delta::diff(&imu(0.0), &imu(90.0)).unwrap();
+    assert_eq!(
+        delta.0.keys().map(String::as_str).collect::<Vec<_>>(),
+        ["euler", "heading", "rotation"]
+    );
+    assert_eq!(delta.0["heading"], FieldValue::Float(90.0));
+    assert_eq!(
+        delta.0["euler"],
+        FieldValue::Object(BTreeMap::from([
+            ("pitch".to_string(), FieldValue::Float(0.0)),
+            ("roll".to_string(), FieldValue::Float(0.0)),
+            ("yaw".to_string(), FieldValue::Float(90.0)),
+        ]))
+    );
+}
+
+#[test]
+fn diff_of_different_kinds_is_none() {
+    assert_eq!(delta::diff(&imu(0.0), &distance(None)), None);
+}
+
+#[test]
+fn apply_accepts_integers_for_floats() {
+    let updated = delta::apply(&imu(0.0), &delta([("heading", FieldValue::Integer(45))])).unwrap();
+    let DeviceStatus::Imu { heading, .. } = updated else {
+        panic!("expected an IMU status, got {updated:?}");
+    };
+    assert_eq!(heading, 45.0);
+}
+
+#[test]
+fn apply_ignores_unknown_fields() {
+    let updated = delta::apply(
+        &distance(None),
+        &delta([("not_a_field", FieldValue::Bool(true))]),
+    )
+    .unwrap();
+    assert_eq!(updated, distance(None));
+}
+
+#[test]
+fn apply_rejects_mismatched_types() {
+    for delta in [
+        delta([("heading", FieldValue::String("north".to_string()))]),
+        delta([("calibrating", FieldValue::Integer(1))]),
+        delta([("gyro_rate", FieldValue::Null)]),
+    ] {
+        assert!(delta::apply(&imu(0.0), &delta).is_err(), "{delta:?}");
+    }
+}
+
+#[test]
+fn tracker_applies_deltas() {
+    let mut encoder = DeltaEncoder::new();
+    let mut tracker = RobotStateTracker::new();
+    for heading in [0.0, 30.0, 60.0] {
+        let event = encoder.encode(PORT, imu(heading)).unwrap();
+        assert_eq!(
+            matches!(event, Event::DeviceDelta { .. }),
+            heading != 0.0,
+            "{event:?}"
+        );
+        tracker.apply_event(&event);
+        assert_eq!(tracker.status(PORT), Some(&imu(heading)));
+    }
+}
+
+#[test]
+fn tracker_drops_deltas_that_do_not_fit() {
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_event(&Event::DeviceUpdate {
+        status: imu(0.0),
+        port: PORT,
+    });
+    tracker.apply_event(&Event::DeviceDelta {
+        port: PORT,
+        delta: delta([("heading", FieldValue::Bool(true))]),
+    });
+    assert_eq!(tracker.status(PORT), Some(&imu(0.0)));
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn csv_export_replays_deltas() {
+    use vexide_simulator_protocol::{
+        export,
+        recording::{Entry, Message, Recording, RecordingHeader},
+    };
+
+    let mut recording = Recording::new(RecordingHeader::new(2, 0));
+    let mut encoder = DeltaEncoder::new();
+    for (time_ms, heading) in [(0, 0.0), (10, 45.0)] {
+        recording.entries.push(Entry {
+            time_ms,
+            message: Message::Event(encoder.encode(PORT, imu(heading)).unwrap()),
+        });
+    }
+
+    let mut csv = Vec::new();
+    export::write_csv(&recording, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let rows = |time_ms: &str| {
+        csv.lines()
+            .filter(|line| line.starts_with(&format!("{time_ms},")))
+            .count()
+    };
+    assert!(csv.contains("\n10,Smart port 1,heading,45.0\n"), "{csv}");
+    assert_eq!(rows("0"), rows("10"));
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "framebuffer")]
+
+use std::num::NonZeroU16;
+
+use mint::Point2;
+use vexide_simulator_protocol::{
+    Color, DrawCommand, Event, Framebuffer, Rect, Shape, SCREEN_HEIGHT, SCREEN_WIDTH,
+};
+
+const WHITE: Color = Color(0xFF_FFFF);
+
+fn screen() -> Rect {
+    Rect {
+        top_left: Point2 { x: 0, y: 0 },
+        bottom_right: Point2 {
+            x: SCREEN_WIDTH as i32 - 1,
+            y: SCREEN_HEIGHT as i32 - 1,
+        },
+    }
+}
+
+fn draw(framebuffer: &mut Framebuffer, command: DrawCommand, clip_region: Rect) {
+    framebuffer.apply(&Event::ScreenDraw {
+        command,
+        color: WHITE,
+        clip_region,
+    });
+}
+
+fn lit(framebuffer: &Framebuffer) -> usize {
+    framebuffer
+        .pixels()
+        .iter()
+        .filter(|&&pixel| pixel == WHITE)
+        .count()
+}
+
+fn huge() -> Rect {
+    Rect {
+        top_left: Point2 {
+            x: i32::MIN,
+            y: i32::MIN,
+        },
+        bottom_right: Point2 {
+            x: i32::MAX,
+            y: i32::MAX,
+        },
+    }
+}
+
+#[test]
+fn fill_circle_with_largest_radius() {
+    let mut framebuffer = Framebuffer::new();
+    let shape = Shape::Circle {
+        center: Point2 { x: 10, y: 10 },
+        radius: u16::MAX,
+    };
+    draw(&mut framebuffer, DrawCommand::Fill { shape }, screen());
+    assert_eq!(lit(&framebuffer), framebuffer.pixels().len());
+}
+
+#[test]
+fn stroke_circle_at_extreme_center() {
+    let mut framebuffer = Framebuffer::new();
+    for center in [
+        Point2 {
+            x: i32::MAX,
+            y: i32::MAX,
+        },
+        Point2 {
+            x: i32::MIN,
+            y: i32::MIN,
+        },
+    ] {
+        let shape = Shape::Circle {
+            center,
+            radius: u16::MAX,
+        };
+        draw(&mut framebuffer, DrawCommand::Stroke { shape }, huge());
+        draw(&mut framebuffer, DrawCommand::Fill { shape }, huge());
+    }
+    assert_eq!(lit(&framebuffer), 0);
+}
+
+#[test]
+fn fill_huge_rectangle_is_clipped() {
+    let mut framebuffer = Framebuffer::new();
+    let clip = Rect {
+        top_left: Point2 { x: 10, y: 10 },
+        bottom_right: Point2 { x: 19, y: 14 },
+    };
+    let shape = Shape::Rectangle {
+        top_left: huge().top_left,
+        bottom_right: huge().bottom_right,
+    };
+    draw(&mut framebuffer, DrawCommand::Fill { shape }, clip);
+    assert_eq!(lit(&framebuffer), 50);
+    assert_eq!(framebuffer.pixel(10, 10), Some(WHITE));
+    assert_eq!(framebuffer.pixel(20, 10), Some(Color(0)));
+}
+
+#[test]
+fn stroke_huge_rectangle_draws_nothing_off_screen() {
+    let mut framebuffer = Framebuffer::new();
+    let shape = Shape::Rectangle {
+        top_left: huge().top_left,
+        bottom_right: huge().bottom_right,
+    };
+    draw(&mut framebuffer, DrawCommand::Stroke { shape }, huge());
+    assert_eq!(lit(&framebuffer), 0);
+}
+
+#[test]
+fn line_across_whole_coordinate_space_is_clipped() {
+    let mut framebuffer = Framebuffer::new();
+    let shape = Shape::Line {
+        start: Point2 { x: i32::MIN, y: 5 },
+        end: Point2 { x: i32::MAX, y: 5 },
+    };
+    draw(&mut framebuffer, DrawCommand::Stroke { shape }, huge());
+    assert_eq!(lit(&framebuffer), SCREEN_WIDTH as usize);
+    assert_eq!(framebuffer.pixel(0, 5), Some(WHITE));
+}
+
+#[test]
+fn on_screen_line_is_unchanged_by_clipping() {
+    let mut framebuffer = Framebuffer::new();
+    let shape = Shape::Line {
+        start: Point2 { x: 0, y: 0 },
+        end: Point2 { x: 9, y: 3 },
+    };
+    draw(&mut framebuffer, DrawCommand::Stroke { shape }, screen());
+    assert_eq!(lit(&framebuffer), 10);
+    assert_eq!(framebuffer.pixel(0, 0), Some(WHITE));
+    assert_eq!(framebuffer.pixel(9, 3), Some(WHITE));
+}
+
+#[test]
+fn copy_buffer_with_huge_rectangle() {
+    let mut framebuffer = Framebuffer::new();
+    let command = DrawCommand::CopyBuffer {
+        top_left: huge().top_left,
+        bottom_right: huge().bottom_right,
+        stride: NonZeroU16::MAX,
+        buffer: String::new(),
+    };
+    draw(&mut framebuffer, command, huge());
+    assert_eq!(lit(&framebuffer), 0);
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "codec")]
+
+use std::{
+    io::BufReader,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use vexide_simulator_protocol::codec::{
+    negotiate_as_frontend, negotiate_as_simulator, HandshakeError, HandshakeOptions, Reader, Writer,
+};
+
+type Halves = (Reader<BufReader<TcpStream>>, Writer<TcpStream>);
+
+fn pair() -> (Halves, Halves) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (server, _) = listener.accept().unwrap();
+    let halves = |stream: TcpStream| {
+        (
+            Reader::new(BufReader::new(stream.try_clone().unwrap())),
+            Writer::new(stream),
+        )
+    };
+    (halves(client), halves(server))
+}
+
+fn options(timeout: Duration) -> HandshakeOptions {
+    HandshakeOptions {
+        timeout,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn silent_simulator_times_out() {
+    let ((mut reader, mut writer), _simulator) = pair();
+    let start = Instant::now();
+    let result = negotiate_as_frontend(
+        &mut reader,
+        &mut writer,
+        &options(Duration::from_millis(200)),
+    );
+    assert!(matches!(result, Err(HandshakeError::Timeout)), "{result:?}");
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[test]
+fn silent_frontend_times_out() {
+    let (_frontend, (mut reader, mut writer)) = pair();
+    let result = negotiate_as_simulator(
+        &mut reader,
+        &mut writer,
+        &options(Duration::from_millis(200)),
+    );
+    assert!(matches!(result, Err(HandshakeError::Timeout)), "{result:?}");
+}
+
+#[test]
+fn slow_handshake_within_timeout_succeeds() {
+    let ((mut frontend_reader, mut frontend_writer), (mut reader, mut writer)) = pair();
+    let simulator = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        negotiate_as_simulator(&mut reader, &mut writer, &options(Duration::from_secs(5)))
+    });
+    let negotiated = negotiate_as_frontend(
+        &mut frontend_reader,
+        &mut frontend_writer,
+        &options(Duration::from_secs(5)),
+    )
+    .unwrap();
+    assert_eq!(negotiated, simulator.join().unwrap().unwrap());
+}
+
+#[test]
+fn read_timeout_is_restored() {
+    let ((mut frontend_reader, mut frontend_writer), (mut reader, mut writer)) = pair();
+    let simulator = thread::spawn(move || {
+        negotiate_as_simulator(&mut reader, &mut writer, &options(Duration::from_secs(5)))
+    });
+    negotiate_as_frontend(
+        &mut frontend_reader,
+        &mut frontend_writer,
+        &options(Duration::from_secs(5)),
+    )
+    .unwrap();
+    simulator.join().unwrap().unwrap();
+    assert_eq!(
+        frontend_reader.get_ref().get_ref().read_timeout().unwrap(),
+        None
+    );
+}
+
+#[test]
+fn unsupported_version_is_rejected_on_both_sides() {
+    let ((mut frontend_reader, mut frontend_writer), (mut reader, mut writer)) = pair();
+    let simulator = thread::spawn(move || {
+        let options = HandshakeOptions {
+            versions: vec![2],
+            ..options(Duration::from_secs(5))
+        };
+        negotiate_as_simulator(&mut reader, &mut writer, &options)
+    });
+    let options = HandshakeOptions {
+        versions: vec![1],
+        ..options(Duration::from_secs(5))
+    };
+    let result = negotiate_as_frontend(&mut frontend_reader, &mut frontend_writer, &options);
+    assert!(
+        matches!(result, Err(HandshakeError::Rejected(_))),
+        "{result:?}"
+    );
+    assert!(matches!(
+        simulator.join().unwrap(),
+        Err(HandshakeError::UnsupportedVersion { version: 1 })
+    ));
+}
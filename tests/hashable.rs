@@ -0,0 +1,55 @@
+#![cfg(feature = "codec")]
+
+use std::collections::HashSet;
+
+use vexide_simulator_protocol::{hashable::Hashable, Event};
+
+fn key<T: serde::Serialize>(value: T) -> Hashable<T> {
+    Hashable::new(value).unwrap()
+}
+
+fn pose(x: f64) -> Event {
+    Event::RobotPose { x, y: 1.0 }
+}
+
+#[test]
+fn nan_is_not_none() {
+    assert_ne!(key(Some(f64::NAN)), key(None::<f64>));
+    assert_ne!(key(f64::NAN), key(f64::INFINITY));
+    assert_ne!(key(f64::INFINITY), key(f64::NEG_INFINITY));
+}
+
+#[test]
+fn every_nan_is_equal() {
+    let other_nan = f64::from_bits(f64::NAN.to_bits() ^ 1);
+    assert!(other_nan.is_nan());
+    assert_eq!(key(pose(f64::NAN)), key(pose(other_nan)));
+    assert_eq!(key(pose(f64::NAN)), key(pose(-f64::NAN)));
+}
+
+#[test]
+fn zeros_are_equal() {
+    assert_eq!(key(pose(0.0)), key(pose(-0.0)));
+    assert_ne!(key(pose(0.0)), key(pose(f64::MIN_POSITIVE)));
+}
+
+#[test]
+fn finite_floats_keep_json_form() {
+    assert_eq!(key(pose(1.5)).key(), r#"{"RobotPose":{"x":1.5,"y":1.0}}"#);
+    assert_eq!(key("a \"quoted\" string").key(), r#""a \"quoted\" string""#);
+}
+
+#[test]
+fn works_in_sets() {
+    let set: HashSet<_> = [
+        pose(f64::NAN),
+        pose(f64::NAN),
+        pose(0.0),
+        pose(-0.0),
+        pose(2.0),
+    ]
+    .into_iter()
+    .map(key)
+    .collect();
+    assert_eq!(set.len(), 3);
+}
@@ -0,0 +1,72 @@
+use vexide_simulator_protocol::{
+    playback::{InputTrace, Jitter},
+    Command, ControllerState, ControllerUpdate,
+};
+
+fn state(axis: i32) -> ControllerState {
+    ControllerState {
+        axis1: axis,
+        axis2: axis.wrapping_neg(),
+        axis3: axis,
+        axis4: axis.wrapping_neg(),
+        ..Default::default()
+    }
+}
+
+fn axes(command: &Command) -> Vec<i32> {
+    let Command::ControllerUpdate(Some(ControllerUpdate::Raw(state)), _) = command else {
+        panic!("expected a raw controller update, got {command:?}");
+    };
+    vec![state.axis1, state.axis2, state.axis3, state.axis4]
+}
+
+#[test]
+fn record_skips_unchanged_input() {
+    let mut trace = InputTrace::new();
+    trace.record(0, Some(state(0)), None);
+    trace.record(10, Some(state(0)), None);
+    trace.record(20, Some(state(5)), None);
+    trace.record(30, Some(state(5)), Some(state(0)));
+    let times: Vec<_> = trace.samples.iter().map(|sample| sample.time_ms).collect();
+    assert_eq!(times, [0, 20, 30]);
+}
+
+#[test]
+fn extreme_axis_noise_stays_in_range() {
+    let mut trace = InputTrace::new();
+    for (index, axis) in [0, 127, -127, i32::MAX, i32::MIN].into_iter().enumerate() {
+        trace.record(index as u64, Some(state(axis)), None);
+    }
+    for axis_noise in [i32::MAX, i32::MIN, 1000, 254] {
+        for seed in 0..20 {
+            let jitter = Jitter {
+                timing_ms: u32::MAX,
+                axis_noise,
+                seed,
+            };
+            let mut last_time = 0;
+            for (time_ms, command) in trace.playback(jitter) {
+                assert!(time_ms >= last_time);
+                last_time = time_ms;
+                for axis in axes(&command) {
+                    assert!((-127..=127).contains(&axis), "{axis}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn negative_axis_noise_adds_none() {
+    let mut trace = InputTrace::new();
+    trace.record(0, Some(state(42)), None);
+    let jitter = Jitter {
+        timing_ms: 0,
+        axis_noise: -5,
+        seed: 7,
+    };
+    let commands: Vec<_> = trace.playback(jitter).collect();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0].0, 0);
+    assert_eq!(axes(&commands[0].1), [42, -42, 42, -42]);
+}
@@ -0,0 +1,119 @@
+use mint::Point2;
+use vexide_simulator_protocol::{
+    priority::{Priority, SendQueue},
+    AdiPort, Color, Command, Device, DrawCommand, Event, Port, Rect, SerialData, Shape, SmartPort,
+    TouchEvent,
+};
+
+fn kinds(queue: &mut SendQueue<Event>) -> Vec<&'static str> {
+    std::iter::from_fn(|| queue.pop())
+        .map(|event| event.kind())
+        .collect()
+}
+
+fn draw(display: u32) -> Event {
+    let origin = Point2 { x: 0, y: 0 };
+    Event::DisplayDraw {
+        display,
+        command: DrawCommand::Fill {
+            shape: Shape::Pixel { pos: origin },
+        },
+        color: Color(0),
+        clip_region: Rect {
+            top_left: origin,
+            bottom_right: origin,
+        },
+    }
+}
+
+#[test]
+fn urgent_messages_go_first() {
+    let mut queue = SendQueue::new();
+    queue.push_event(Event::log_info("hello"));
+    queue.push_event(Event::RobotPose { x: 0.0, y: 0.0 });
+    assert_eq!(kinds(&mut queue), ["RobotPose", "Log"]);
+}
+
+#[test]
+fn sync_markers_are_not_reordered() {
+    let mut queue = SendQueue::new();
+    queue.push_event(Event::log_info("hello"));
+    queue.push_event(Event::Sync { seq: 0 });
+    queue.push_event(Event::RobotPose { x: 0.0, y: 0.0 });
+    assert_eq!(kinds(&mut queue), ["Log", "Sync", "RobotPose"]);
+
+    let mut queue = SendQueue::new();
+    queue.push_command(Command::VisionDetections {
+        port: SmartPort(1),
+        objects: Vec::new(),
+    });
+    queue.push_command(Command::Sync { seq: 0 });
+    queue.push_command(Command::Touch {
+        pos: Point2 { x: 0, y: 0 },
+        event: TouchEvent::Pressed,
+    });
+    let kinds: Vec<&str> = std::iter::from_fn(|| queue.pop())
+        .map(|command| command.kind())
+        .collect();
+    assert_eq!(kinds, ["VisionDetections", "Sync", "Touch"]);
+}
+
+#[test]
+fn exited_is_sent_after_pending_output() {
+    let mut queue = SendQueue::new();
+    queue.push_event(Event::log_info("hello"));
+    queue.push_event(Event::Serial(SerialData::new(1, b"world")));
+    queue.push_event(Event::Exited);
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.len_of(Priority::Control), 1);
+    assert_eq!(kinds(&mut queue), ["Log", "Serial", "Exited"]);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn display_closed_is_sent_after_its_draws() {
+    let mut queue = SendQueue::new();
+    queue.push_event(draw(1));
+    queue.push_event(Event::DisplayRender { display: 1 });
+    queue.push_event(Event::DisplayClosed { display: 1 });
+    queue.push_event(Event::Sync { seq: 0 });
+    assert_eq!(
+        kinds(&mut queue),
+        ["DisplayDraw", "DisplayRender", "DisplayClosed", "Sync"]
+    );
+}
+
+#[test]
+fn inputs_do_not_cross_device_configuration() {
+    let port = AdiPort(1);
+    let input = |voltage| Command::AdiInput {
+        port,
+        voltage,
+        expander: None,
+    };
+    let mut queue = SendQueue::new();
+    queue.push_command(input(1.0));
+    queue.push_command(Command::ConfigureDevice {
+        port: Port::Adi(port),
+        device: Device::AdiLineTracker,
+    });
+    queue.push_command(input(2.0));
+    queue.push_command(Command::SetEncoderPosition {
+        port: SmartPort(1),
+        position: 0.0,
+    });
+
+    let popped: Vec<Command> = std::iter::from_fn(|| queue.pop()).collect();
+    let kinds: Vec<&str> = popped.iter().map(Command::kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            "AdiInput",
+            "ConfigureDevice",
+            "SetEncoderPosition",
+            "AdiInput"
+        ]
+    );
+    assert_eq!(popped[0], input(1.0));
+    assert_eq!(popped[3], input(2.0));
+}
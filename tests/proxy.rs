@@ -0,0 +1,89 @@
+#![cfg(feature = "codec")]
+
+use std::io::Cursor;
+
+use mint::Point2;
+use serde_json::json;
+use vexide_simulator_protocol::{
+    codec::{self, Reader, Writer},
+    proxy::Proxy,
+    v1, Color, Command, DrawCommand, Event, Rect, TextBackground, TextLocation, TouchEvent, V5Text,
+};
+
+fn stream<T: serde::Serialize>(messages: &[T]) -> Reader<Cursor<String>> {
+    let lines = messages
+        .iter()
+        .map(|message| codec::encode(message).unwrap())
+        .collect();
+    Reader::new(Cursor::new(lines))
+}
+
+fn lines(writer: Writer<Vec<u8>>) -> Vec<serde_json::Value> {
+    String::from_utf8(writer.into_inner())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn filters_run_in_order() {
+    let reader = stream(&[Event::log_info("a"), Event::Ready, Event::log_info("b")]);
+    let mut proxy = Proxy::events(reader, Writer::new(Vec::new()))
+        .with_filter(|event| match event {
+            Event::Log { message, .. } => Some(Event::log_info(message.to_uppercase())),
+            event => Some(event),
+        })
+        .with_filter(|event| match &event {
+            Event::Log { message, .. } if message == "B" => None,
+            _ => Some(event),
+        });
+    proxy.run().unwrap();
+    let (_, writer) = proxy.into_inner();
+    assert_eq!(
+        lines(writer),
+        [
+            serde_json::to_value(Event::log_info("A")).unwrap(),
+            serde_json::to_value(Event::Ready).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn forward_reports_end_of_stream() {
+    let reader = stream(&[Command::Touch {
+        pos: Point2 { x: 1, y: 2 },
+        event: TouchEvent::Pressed,
+    }]);
+    let mut proxy =
+        Proxy::commands(reader, Writer::new(Vec::new())).with_filter(|_| None::<Command>);
+    assert!(proxy.forward().unwrap());
+    assert!(!proxy.forward().unwrap());
+    let (_, writer) = proxy.into_inner();
+    assert!(writer.into_inner().is_empty());
+}
+
+#[test]
+fn events_are_encoded_for_writer_version() {
+    let event = Event::ScreenDraw {
+        command: DrawCommand::Write {
+            text: V5Text::default(),
+            location: TextLocation::Line { line: 1 },
+            background: TextBackground::Solid(Color(0x00FF00)),
+        },
+        color: Color(0xFFFFFF),
+        clip_region: Rect {
+            top_left: Point2 { x: 0, y: 0 },
+            bottom_right: Point2 { x: 479, y: 271 },
+        },
+    };
+    let mut writer = Writer::new(Vec::new());
+    writer.set_version(v1::VERSION);
+    let mut proxy = Proxy::events(stream(&[event]), writer);
+    proxy.run().unwrap();
+    let (_, writer) = proxy.into_inner();
+    let lines = lines(writer);
+    let command = &lines[0]["ScreenDraw"]["command"]["Write"];
+    assert_eq!(command["opaque"], json!(true));
+    assert_eq!(command["background"], json!(0x00FF00));
+}
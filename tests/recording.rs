@@ -0,0 +1,181 @@
+use mint::Point2;
+use vexide_simulator_protocol::{
+    recording::{Entry, Message, Recording, RecordingHeader},
+    Color, DeviceStatus, DrawCommand, Event, Port, Rect, Shape, SmartPort,
+};
+
+const SCREEN: Rect = Rect {
+    top_left: Point2 { x: 0, y: 0 },
+    bottom_right: Point2 { x: 479, y: 271 },
+};
+
+fn clear() -> Event {
+    Event::ScreenClear {
+        color: Color(0),
+        clip_region: SCREEN,
+    }
+}
+
+fn draw(x: i32) -> Event {
+    Event::ScreenDraw {
+        command: DrawCommand::Fill {
+            shape: Shape::Pixel {
+                pos: Point2 { x, y: 0 },
+            },
+        },
+        color: Color(0xFFFFFF),
+        clip_region: SCREEN,
+    }
+}
+
+fn double_buffer(enable: bool) -> Event {
+    Event::ScreenDoubleBufferMode { enable }
+}
+
+/// A recording with one event sent each millisecond, starting at 1.
+fn recording(events: impl IntoIterator<Item = Event>) -> Recording {
+    let mut recording = Recording::new(RecordingHeader::new(2, 0));
+    recording.entries = events
+        .into_iter()
+        .zip(1..)
+        .map(|(event, time_ms)| Entry {
+            time_ms,
+            message: Message::Event(event),
+        })
+        .collect();
+    recording
+}
+
+#[test]
+fn clear_discards_earlier_screen_events() {
+    let recording = recording([draw(1), clear(), draw(2)]);
+    assert_eq!(recording.seek_to(0).screen, []);
+    assert_eq!(recording.seek_to(1).screen, [draw(1)]);
+    assert_eq!(recording.seek_to(3).screen, [clear(), draw(2)]);
+}
+
+#[test]
+fn partial_clear_keeps_earlier_screen_events() {
+    let partial = Event::ScreenClear {
+        color: Color(0),
+        clip_region: Rect {
+            top_left: Point2 { x: 0, y: 0 },
+            bottom_right: Point2 { x: 10, y: 10 },
+        },
+    };
+    let recording = recording([draw(1), partial.clone()]);
+    assert_eq!(recording.seek_to(2).screen, [draw(1), partial]);
+}
+
+#[test]
+fn clear_keeps_buffering_mode() {
+    let recording = recording([double_buffer(false), draw(1), clear(), draw(2)]);
+    assert_eq!(
+        recording.seek_to(4).screen,
+        [double_buffer(false), clear(), draw(2)]
+    );
+}
+
+#[test]
+fn double_buffered_clear_waits_for_render() {
+    let recording = recording([
+        draw(1),
+        double_buffer(true),
+        draw(2),
+        Event::ScreenRender,
+        clear(),
+        draw(3),
+        Event::ScreenRender,
+        clear(),
+        draw(4),
+    ]);
+    // The visible frame is still the one rendered before the clear.
+    assert_eq!(
+        recording.seek_to(6).screen,
+        [
+            draw(1),
+            double_buffer(true),
+            draw(2),
+            Event::ScreenRender,
+            clear(),
+            draw(3),
+        ]
+    );
+    assert_eq!(
+        recording.seek_to(7).screen,
+        [double_buffer(true), clear(), draw(3), Event::ScreenRender]
+    );
+    assert_eq!(
+        recording.seek_to(9).screen,
+        [
+            double_buffer(true),
+            clear(),
+            draw(3),
+            Event::ScreenRender,
+            clear(),
+            draw(4),
+        ]
+    );
+}
+
+#[test]
+fn disabling_double_buffering_clears_directly() {
+    let recording = recording([double_buffer(true), clear(), double_buffer(false), clear()]);
+    assert_eq!(recording.seek_to(4).screen, [double_buffer(false), clear()]);
+}
+
+#[test]
+fn seek_replays_device_state() {
+    let status = |power| DeviceStatus::Electromagnet {
+        power,
+        holding: false,
+    };
+    let port = Port::Smart(SmartPort(1));
+    let recording = recording([
+        Event::DeviceUpdate {
+            status: status(0.5),
+            port,
+        },
+        Event::DeviceUpdate {
+            status: status(1.0),
+            port,
+        },
+    ]);
+    assert_eq!(recording.seek_to(0).robot.status(port), None);
+    assert_eq!(recording.seek_to(1).robot.status(port), Some(&status(0.5)));
+    assert_eq!(recording.seek_to(5).robot.status(port), Some(&status(1.0)));
+}
+
+#[test]
+fn position_includes_entries_at_time() {
+    let mut recording = recording([draw(1), draw(2)]);
+    recording.entries[1].time_ms = 1;
+    assert_eq!(recording.position(0), 0);
+    assert_eq!(recording.position(1), 2);
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn index_points_at_entry_lines() {
+    use vexide_simulator_protocol::{codec, recording::RecordingIndex};
+
+    let recording = recording([draw(1), draw(2), draw(3)]);
+    let mut file = Vec::new();
+    recording.write_to(&mut file).unwrap();
+    let index = RecordingIndex::build(file.as_slice()).unwrap();
+
+    assert_eq!(index.entries().len(), 3);
+    for (indexed, entry) in index.entries().iter().zip(&recording.entries) {
+        assert_eq!(indexed.time_ms, entry.time_ms);
+        let line = file[indexed.offset as usize..]
+            .split(|&byte| byte == b'\n')
+            .next()
+            .unwrap();
+        let line = std::str::from_utf8(line).unwrap();
+        assert_eq!(&codec::decode::<Entry>(line).unwrap(), entry);
+    }
+
+    assert_eq!(index.offset_after(0), Some(index.entries()[0].offset));
+    assert_eq!(index.offset_after(2), Some(index.entries()[2].offset));
+    assert_eq!(index.offset_after(3), None);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "codec")]
+
+use std::{
+    io::{BufReader, Write},
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use vexide_simulator_protocol::{
+    codec::{self, Reader, Writer},
+    scenario::{Assertion, Scenario, ScenarioError, Step},
+    Event,
+};
+
+fn expect_exit(timeout_ms: u64) -> Scenario {
+    Scenario {
+        name: None,
+        steps: vec![Step::Expect {
+            assertion: Assertion::Exited,
+            timeout_ms,
+        }],
+    }
+}
+
+/// A connection to a simulator played by `simulator`, which is given the other end.
+fn run(
+    scenario: &Scenario,
+    simulator: impl FnOnce(TcpStream) + Send + 'static,
+) -> Result<Vec<Event>, ScenarioError> {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (peer, _) = listener.accept().unwrap();
+    let simulator = thread::spawn(move || simulator(peer));
+    let mut reader = Reader::new(BufReader::new(stream.try_clone().unwrap()));
+    let mut writer = Writer::new(stream);
+    let result = scenario.run(&mut reader, &mut writer);
+    simulator.join().unwrap();
+    result
+}
+
+#[test]
+fn silent_simulator_times_out() {
+    let start = Instant::now();
+    let result = run(&expect_exit(200), |peer| {
+        // Keep the connection open without sending anything.
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(1000));
+            drop(peer);
+        });
+    });
+    assert!(
+        matches!(result, Err(ScenarioError::Timeout { step: 0 })),
+        "{result:?}"
+    );
+    assert!(start.elapsed() < Duration::from_millis(900));
+}
+
+#[test]
+fn chatty_simulator_times_out() {
+    let result = run(&expect_exit(200), |mut peer| {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_millis(500) {
+            if peer
+                .write_all(codec::encode(&Event::Ready).unwrap().as_bytes())
+                .is_err()
+            {
+                break;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+    assert!(
+        matches!(result, Err(ScenarioError::Timeout { step: 0 })),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn expected_event_within_timeout() {
+    let result = run(&expect_exit(5_000), |mut peer| {
+        thread::sleep(Duration::from_millis(100));
+        for event in [Event::Ready, Event::Exited] {
+            peer.write_all(codec::encode(&event).unwrap().as_bytes())
+                .unwrap();
+        }
+    });
+    assert_eq!(result.unwrap(), [Event::Ready, Event::Exited]);
+}
+
+#[test]
+fn longest_timeout_waits_for_event() {
+    let result = run(&expect_exit(u64::MAX), |mut peer| {
+        peer.write_all(codec::encode(&Event::Exited).unwrap().as_bytes())
+            .unwrap();
+    });
+    assert_eq!(result.unwrap(), [Event::Exited]);
+}
+
+#[test]
+fn disconnect_fails_step() {
+    let result = run(&expect_exit(5_000), drop);
+    assert!(
+        matches!(result, Err(ScenarioError::Disconnected { step: 0 })),
+        "{result:?}"
+    );
+}
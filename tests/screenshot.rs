@@ -0,0 +1,41 @@
+use base64::prelude::*;
+use rgb::RGB8;
+use vexide_simulator_protocol::{Screenshot, ScreenshotError};
+
+fn screenshot(bytes: &[u8]) -> Screenshot {
+    Screenshot {
+        width: 1,
+        height: 1,
+        buffer: BASE64_STANDARD.encode(bytes),
+    }
+}
+
+#[test]
+fn pixels_round_trip() {
+    let pixels = [RGB8::new(1, 2, 3), RGB8::new(250, 251, 252)];
+    assert_eq!(Screenshot::new(2, 1, &pixels).to_pixels().unwrap(), pixels);
+    assert_eq!(screenshot(&[]).to_pixels().unwrap(), []);
+}
+
+#[test]
+fn partial_pixel_is_an_error() {
+    for len in [1, 2, 4, 5] {
+        assert_eq!(
+            screenshot(&vec![0; len]).to_pixels(),
+            Err(ScreenshotError::PartialPixel { len })
+        );
+    }
+}
+
+#[test]
+fn invalid_base64_is_an_error() {
+    let screenshot = Screenshot {
+        width: 1,
+        height: 1,
+        buffer: "not base64!".to_string(),
+    };
+    assert!(matches!(
+        screenshot.to_pixels(),
+        Err(ScreenshotError::Decode(_))
+    ));
+}
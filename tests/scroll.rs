@@ -0,0 +1,231 @@
+use mint::Point2;
+use vexide_simulator_protocol::{Rect, ScrollLocation, LINE_HEIGHT, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+const RIGHT: i32 = SCREEN_WIDTH as i32 - 1;
+const BOTTOM: i32 = SCREEN_HEIGHT as i32 - 1;
+
+fn rect(left: i32, top: i32, right: i32, bottom: i32) -> Rect {
+    Rect {
+        top_left: Point2 { x: left, y: top },
+        bottom_right: Point2 {
+            x: right,
+            y: bottom,
+        },
+    }
+}
+
+fn rectangle(left: i32, top: i32, right: i32, bottom: i32) -> ScrollLocation {
+    ScrollLocation::Rectangle {
+        top_left: Point2 { x: left, y: top },
+        bottom_right: Point2 {
+            x: right,
+            y: bottom,
+        },
+    }
+}
+
+/// Checks that the moved and filled rows of a scroll exactly cover its region.
+#[track_caller]
+fn assert_covers(location: &ScrollLocation, lines: i32) {
+    let regions = location.regions(lines).unwrap();
+    let region = regions.region;
+    let rows = |rect: Option<Rect>| {
+        rect.map_or(0, |rect| {
+            assert_eq!(rect.top_left.x, region.top_left.x);
+            assert_eq!(rect.bottom_right.x, region.bottom_right.x);
+            assert!(
+                rect.top_left.y >= region.top_left.y,
+                "{rect:?} in {region:?}"
+            );
+            assert!(
+                rect.bottom_right.y <= region.bottom_right.y,
+                "{rect:?} in {region:?}"
+            );
+            rect.bottom_right.y - rect.top_left.y + 1
+        })
+    };
+    assert_eq!(rows(regions.source), rows(regions.destination));
+    assert_eq!(
+        rows(regions.destination) + rows(regions.fill),
+        region.bottom_right.y - region.top_left.y + 1,
+        "scroll by {lines} in {region:?}: {regions:?}"
+    );
+}
+
+#[test]
+fn scroll_up() {
+    let regions = rectangle(10, 10, 50, 50).regions(5).unwrap();
+    assert_eq!(regions.source, Some(rect(10, 15, 50, 50)));
+    assert_eq!(regions.destination, Some(rect(10, 10, 50, 45)));
+    assert_eq!(regions.fill, Some(rect(10, 46, 50, 50)));
+}
+
+#[test]
+fn scroll_down_with_negative_lines() {
+    let regions = rectangle(10, 10, 50, 50).regions(-5).unwrap();
+    assert_eq!(regions.source, Some(rect(10, 10, 50, 45)));
+    assert_eq!(regions.destination, Some(rect(10, 15, 50, 50)));
+    assert_eq!(regions.fill, Some(rect(10, 10, 50, 14)));
+}
+
+#[test]
+fn scroll_by_zero_moves_nothing() {
+    let regions = rectangle(10, 10, 50, 50).regions(0).unwrap();
+    assert_eq!(regions.source, regions.destination);
+    assert_eq!(regions.fill, None);
+}
+
+#[test]
+fn scroll_by_one_less_than_height_keeps_one_row() {
+    // The region is 41 rows tall.
+    let regions = rectangle(10, 10, 50, 50).regions(40).unwrap();
+    assert_eq!(regions.source, Some(rect(10, 50, 50, 50)));
+    assert_eq!(regions.destination, Some(rect(10, 10, 50, 10)));
+
+    let regions = rectangle(10, 10, 50, 50).regions(-40).unwrap();
+    assert_eq!(regions.source, Some(rect(10, 10, 50, 10)));
+    assert_eq!(regions.destination, Some(rect(10, 50, 50, 50)));
+}
+
+#[test]
+fn scroll_by_height_or_more_fills_region() {
+    for lines in [41, -41, 42, -42, 1000, -1000, i32::MAX, i32::MIN] {
+        let regions = rectangle(10, 10, 50, 50).regions(lines).unwrap();
+        assert_eq!(regions.source, None, "{lines}");
+        assert_eq!(regions.destination, None, "{lines}");
+        assert_eq!(regions.fill, Some(rect(10, 10, 50, 50)), "{lines}");
+    }
+}
+
+#[test]
+fn single_row_region() {
+    assert_eq!(rectangle(0, 7, 9, 7).regions(0).unwrap().fill, None);
+    for lines in [1, -1] {
+        let regions = rectangle(0, 7, 9, 7).regions(lines).unwrap();
+        assert_eq!(regions.source, None);
+        assert_eq!(regions.fill, Some(rect(0, 7, 9, 7)));
+    }
+}
+
+#[test]
+fn partly_off_screen_region_is_clamped() {
+    let location = rectangle(-100, -100, 1000, 1000);
+    let regions = location.regions(10).unwrap();
+    assert_eq!(regions.region, rect(0, 0, RIGHT, BOTTOM));
+    assert_eq!(regions.source, Some(rect(0, 10, RIGHT, BOTTOM)));
+    assert_eq!(regions.fill, Some(rect(0, BOTTOM - 9, RIGHT, BOTTOM)));
+
+    // Only the on-screen rows count toward the region's height.
+    let location = rectangle(-5, 260, 20, 400);
+    assert_eq!(location.regions(12).unwrap().source, None);
+    assert_eq!(
+        location.regions(11).unwrap().source,
+        Some(rect(0, BOTTOM, 20, BOTTOM))
+    );
+}
+
+#[test]
+fn fully_off_screen_region_is_none() {
+    for location in [
+        rectangle(-50, 10, -1, 50),
+        rectangle(10, -50, 50, -1),
+        rectangle(SCREEN_WIDTH as i32, 10, 1000, 50),
+        rectangle(10, SCREEN_HEIGHT as i32, 50, 1000),
+        rectangle(i32::MIN, i32::MIN, i32::MIN, i32::MIN),
+        rectangle(i32::MAX, i32::MAX, i32::MAX, i32::MAX),
+        // Inverted corners contain no pixels.
+        rectangle(50, 50, 10, 10),
+        ScrollLocation::Line { line: i32::MAX },
+        ScrollLocation::Line {
+            line: SCREEN_HEIGHT as i32 / LINE_HEIGHT + 1,
+        },
+    ] {
+        for lines in [0, 1, -1, i32::MAX, i32::MIN] {
+            assert_eq!(location.regions(lines), None, "{location:?} by {lines}");
+        }
+    }
+}
+
+#[test]
+fn negative_line_starts_at_top() {
+    let regions = ScrollLocation::Line { line: -3 }.regions(0).unwrap();
+    assert_eq!(regions.region, rect(0, 0, RIGHT, BOTTOM));
+
+    let regions = ScrollLocation::Line { line: i32::MIN }.regions(0).unwrap();
+    assert_eq!(regions.region, rect(0, 0, RIGHT, BOTTOM));
+}
+
+#[test]
+fn moved_and_filled_rows_cover_region() {
+    for location in [
+        rectangle(10, 10, 50, 50),
+        rectangle(0, 0, RIGHT, 0),
+        rectangle(-100, 250, 1000, 1000),
+        ScrollLocation::Line { line: 2 },
+        ScrollLocation::Line { line: -1 },
+    ] {
+        for lines in (-300..=300).chain([i32::MIN, i32::MIN + 1, i32::MAX]) {
+            assert_covers(&location, lines);
+        }
+    }
+}
+
+#[cfg(feature = "framebuffer")]
+mod framebuffer {
+    use vexide_simulator_protocol::{Color, Event, Framebuffer};
+
+    use super::*;
+
+    const WHITE: Color = Color(0xFF_FFFF);
+    const RED: Color = Color(0xFF_0000);
+
+    fn scroll(framebuffer: &mut Framebuffer, lines: i32, clip_region: Rect) {
+        framebuffer.apply(&Event::ScreenScroll {
+            location: rectangle(0, 0, RIGHT, BOTTOM),
+            lines,
+            background: RED,
+            clip_region,
+        });
+    }
+
+    fn white() -> Framebuffer {
+        let mut framebuffer = Framebuffer::new();
+        framebuffer.apply(&Event::ScreenScroll {
+            location: rectangle(0, 0, RIGHT, BOTTOM),
+            lines: i32::MAX,
+            background: WHITE,
+            clip_region: rect(0, 0, RIGHT, BOTTOM),
+        });
+        framebuffer
+    }
+
+    #[test]
+    fn clip_partly_off_screen() {
+        let mut framebuffer = white();
+        scroll(&mut framebuffer, -10, rect(-100, -100, 9, 9));
+        for y in 0..SCREEN_HEIGHT as i32 {
+            for x in 0..SCREEN_WIDTH as i32 {
+                let expected = if x <= 9 && y <= 9 { RED } else { WHITE };
+                assert_eq!(framebuffer.pixel(x, y), Some(expected), "({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn clip_fully_off_screen() {
+        for clip in [
+            rect(-100, -100, -1, -1),
+            rect(SCREEN_WIDTH as i32, 0, i32::MAX, BOTTOM),
+            rect(i32::MIN, i32::MIN, i32::MIN, i32::MIN),
+        ] {
+            for lines in [1, -1, i32::MAX, i32::MIN] {
+                let mut framebuffer = white();
+                scroll(&mut framebuffer, lines, clip);
+                assert!(
+                    framebuffer.pixels().iter().all(|&pixel| pixel == WHITE),
+                    "{clip:?} by {lines}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use vexide_simulator_protocol::{
+    state::RobotStateTracker, AdiPort, Command, Event, Port, SmartPort,
+};
+
+fn input(expander: Option<SmartPort>, port: u8, voltage: f64) -> Command {
+    Command::AdiInput {
+        port: AdiPort(port),
+        voltage,
+        expander,
+    }
+}
+
+#[test]
+fn adi_inputs_are_addressed_by_port() {
+    let expander_port = Port::ExpanderAdi {
+        expander: SmartPort(4),
+        port: AdiPort(1),
+    };
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_command(&input(None, 1, 5.0));
+    tracker.apply_command(&input(Some(SmartPort(4)), 1, 2.5));
+
+    assert_eq!(tracker.adi_input(Port::Adi(AdiPort(1))), Some(5.0));
+    assert_eq!(tracker.adi_input(expander_port), Some(2.5));
+    assert_eq!(
+        tracker.adi_input(Port::ExpanderAdi {
+            expander: SmartPort(5),
+            port: AdiPort(1),
+        }),
+        None
+    );
+}
+
+#[test]
+fn adi_port_helper() {
+    assert_eq!(Port::adi(None, AdiPort(3)), Port::Adi(AdiPort(3)));
+    assert_eq!(
+        Port::adi(Some(SmartPort(2)), AdiPort(3)),
+        Port::ExpanderAdi {
+            expander: SmartPort(2),
+            port: AdiPort(3),
+        }
+    );
+}
+
+#[test]
+fn adi_outputs_are_addressed_by_port() {
+    let port = Port::ExpanderAdi {
+        expander: SmartPort(4),
+        port: AdiPort(2),
+    };
+    let mut tracker = RobotStateTracker::new();
+    tracker.apply_event(&Event::AdiOutput { port, high: true });
+
+    assert_eq!(tracker.adi_output(port), Some(true));
+    assert_eq!(tracker.adi_output(Port::Adi(AdiPort(2))), None);
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use mint::Point2;
+use vexide_simulator_protocol::{
+    stats::{KindStats, SessionStats},
+    Command, Event, TouchEvent,
+};
+
+fn touch() -> Command {
+    Command::Touch {
+        pos: Point2 { x: 0, y: 0 },
+        event: TouchEvent::Pressed,
+    }
+}
+
+#[test]
+fn totals_by_kind() {
+    let mut stats = SessionStats::new();
+    stats.record_event(&Event::Ready, 10);
+    stats.record_event(&Event::log_info("a"), 40);
+    stats.record_event(&Event::log_info("b"), 60);
+
+    assert_eq!(
+        stats.event("Log"),
+        KindStats {
+            count: 2,
+            bytes: 100
+        }
+    );
+    assert_eq!(
+        stats.event("Ready"),
+        KindStats {
+            count: 1,
+            bytes: 10
+        }
+    );
+    assert_eq!(stats.event("Exited"), KindStats::default());
+    assert_eq!(
+        stats.total_events(),
+        KindStats {
+            count: 3,
+            bytes: 110
+        }
+    );
+    assert_eq!(stats.total_commands(), KindStats::default());
+}
+
+#[test]
+fn kinds_sorted_by_bytes() {
+    let mut stats = SessionStats::new();
+    stats.record_event(&Event::Ready, 10);
+    stats.record_event(&Event::log_info("a"), 40);
+    stats.record_event(&Event::Exited, 20);
+    stats.record_command(&Command::StartExecution, 5);
+    for _ in 0..3 {
+        stats.record_command(&touch(), 30);
+    }
+
+    let kinds = |sorted: Vec<(&'static str, KindStats)>| -> Vec<&str> {
+        sorted.into_iter().map(|(kind, _)| kind).collect()
+    };
+    assert_eq!(kinds(stats.events_by_bytes()), ["Log", "Exited", "Ready"]);
+    assert_eq!(
+        stats.commands_by_bytes(),
+        [
+            (
+                "Touch",
+                KindStats {
+                    count: 3,
+                    bytes: 90
+                }
+            ),
+            ("StartExecution", KindStats { count: 1, bytes: 5 }),
+        ]
+    );
+}
+
+#[test]
+fn rates_over_elapsed_time() {
+    let stats = KindStats {
+        count: 4,
+        bytes: 1000,
+    };
+    assert_eq!(stats.message_rate(Duration::from_secs(2)), 2.0);
+    assert_eq!(stats.byte_rate(Duration::from_secs(2)), 500.0);
+    assert_eq!(stats.message_rate(Duration::ZERO), 0.0);
+}
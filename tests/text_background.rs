@@ -0,0 +1,83 @@
+use serde_json::json;
+use vexide_simulator_protocol::{v1, Color, DrawCommand, TextBackground, TextLocation, V5Text};
+
+fn write(background: TextBackground) -> DrawCommand {
+    DrawCommand::Write {
+        text: V5Text::default(),
+        location: TextLocation::Line { line: 0 },
+        background,
+    }
+}
+
+/// Decodes a `Write` command with the given extra fields, checks that it has `expected` as its
+/// background, and that it survives a round trip through both the current and version 1
+/// encodings.
+#[track_caller]
+fn assert_row(fields: serde_json::Value, expected: TextBackground) {
+    let mut write_json = json!({
+        "text": V5Text::default(),
+        "location": { "Line": { "line": 0 } },
+    });
+    write_json
+        .as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    let command: DrawCommand = serde_json::from_value(json!({ "Write": write_json })).unwrap();
+    assert_eq!(command, write(expected));
+
+    let current = serde_json::to_string(&command).unwrap();
+    assert_eq!(
+        serde_json::from_str::<DrawCommand>(&current).unwrap(),
+        command
+    );
+
+    let legacy = serde_json::to_string(&v1::DrawCommand::from(command.clone())).unwrap();
+    assert_eq!(
+        serde_json::from_str::<DrawCommand>(&legacy).unwrap(),
+        command
+    );
+    assert_eq!(
+        DrawCommand::from(serde_json::from_str::<v1::DrawCommand>(&legacy).unwrap()),
+        command
+    );
+}
+
+#[test]
+fn transparent() {
+    assert_row(
+        json!({ "background": "Transparent" }),
+        TextBackground::Transparent,
+    );
+}
+
+#[test]
+fn solid() {
+    assert_row(
+        json!({ "background": { "Solid": 255 } }),
+        TextBackground::Solid(Color(255)),
+    );
+}
+
+#[test]
+fn legacy_not_opaque() {
+    assert_row(
+        json!({ "opaque": false, "background": 255 }),
+        TextBackground::Transparent,
+    );
+}
+
+#[test]
+fn legacy_opaque() {
+    assert_row(
+        json!({ "opaque": true, "background": 255 }),
+        TextBackground::Solid(Color(255)),
+    );
+}
+
+#[test]
+fn legacy_without_opaque_flag() {
+    assert_row(
+        json!({ "background": 255 }),
+        TextBackground::Solid(Color(255)),
+    );
+}
@@ -0,0 +1,234 @@
+use mint::Point2;
+use serde_json::json;
+use vexide_simulator_protocol::{
+    v1, Color, DrawCommand, Event, Rect, TextBackground, TextLocation, V5Text,
+};
+
+const CLIP: Rect = Rect {
+    top_left: Point2 { x: 0, y: 0 },
+    bottom_right: Point2 { x: 479, y: 271 },
+};
+
+fn write(background: TextBackground) -> Event {
+    Event::ScreenDraw {
+        command: DrawCommand::Write {
+            text: V5Text::default(),
+            location: TextLocation::Line { line: 1 },
+            background,
+        },
+        color: Color(0xFFFFFF),
+        clip_region: CLIP,
+    }
+}
+
+fn command_json<T: serde::Serialize>(event: &T) -> serde_json::Value {
+    serde_json::to_value(event).unwrap()["ScreenDraw"]["command"]["Write"].clone()
+}
+
+#[test]
+fn v1_writes_opaque_flag() {
+    let solid = v1::Event::from(write(TextBackground::Solid(Color(0x00FF00))));
+    let json = command_json(&solid);
+    assert_eq!(json["opaque"], json!(true));
+    assert_eq!(json["background"], json!(0x00FF00));
+
+    let transparent = v1::Event::from(write(TextBackground::Transparent));
+    let json = command_json(&transparent);
+    assert_eq!(json["opaque"], json!(false));
+    assert_eq!(json["background"], json!(0));
+}
+
+#[test]
+fn current_version_writes_text_background() {
+    let json = command_json(&write(TextBackground::Solid(Color(0x00FF00))));
+    assert_eq!(json["background"], json!({ "Solid": 0x00FF00 }));
+    assert!(json.get("opaque").is_none());
+}
+
+#[test]
+fn conversion_round_trips() {
+    for background in [TextBackground::Transparent, TextBackground::Solid(Color(7))] {
+        let event = write(background);
+        assert_eq!(Event::from(v1::Event::from(event.clone())), event);
+    }
+}
+
+#[test]
+fn transparent_v1_text_discards_background() {
+    let event = v1::Event::ScreenDraw {
+        command: v1::DrawCommand::Write {
+            text: V5Text::default(),
+            location: TextLocation::Line { line: 1 },
+            opaque: false,
+            background: Color(0x123456),
+        },
+        color: Color(0xFFFFFF),
+        clip_region: CLIP,
+    };
+    assert_eq!(Event::from(event), write(TextBackground::Transparent));
+}
+
+#[test]
+fn v1_events_are_readable_as_current_events() {
+    let event = v1::Event::from(write(TextBackground::Solid(Color(7))));
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Event>(&json).unwrap(),
+        write(TextBackground::Solid(Color(7)))
+    );
+}
+
+#[test]
+fn unchanged_events_have_the_same_encoding() {
+    let event = Event::log_info("hello");
+    assert_eq!(
+        serde_json::to_value(v1::Event::from(event.clone())).unwrap(),
+        serde_json::to_value(event).unwrap()
+    );
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn writer_encodes_for_its_version() {
+    use vexide_simulator_protocol::codec::Writer;
+
+    let event = write(TextBackground::Solid(Color(7)));
+    let mut writer = Writer::new(Vec::new());
+    writer.set_version(v1::VERSION);
+    writer.write_event(&event).unwrap();
+    let line = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(
+        serde_json::from_str::<v1::Event>(&line).unwrap(),
+        v1::Event::from(event)
+    );
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn handshake_selects_version_1_for_older_frontends() {
+    use std::io::BufReader;
+
+    use vexide_simulator_protocol::{
+        codec::{negotiate_as_simulator, HandshakeOptions, Reader, Writer},
+        Command,
+    };
+
+    let handshake = serde_json::to_string(&Command::Handshake {
+        version: v1::VERSION,
+        extensions: Vec::new(),
+        frame_rate: None,
+    })
+    .unwrap()
+        + "\n";
+    let mut reader = Reader::new(BufReader::new(handshake.as_bytes()));
+    let mut writer = Writer::new(Vec::new());
+    let negotiated =
+        negotiate_as_simulator(&mut reader, &mut writer, &HandshakeOptions::default()).unwrap();
+    assert_eq!(negotiated.version, v1::VERSION);
+    assert_eq!(writer.version(), v1::VERSION);
+}
+
+/// A motor status as version 1 defined it, which version 1 frontends require in full.
+#[cfg(feature = "codec")]
+#[derive(Debug, PartialEq, serde::Deserialize)]
+enum BaselineStatus {
+    Motor {
+        velocity: f64,
+        reversed: bool,
+        power_draw: f64,
+        torque_output: f64,
+        flags: i32,
+        position: f64,
+        target_position: f64,
+        voltage: f64,
+        gearset: vexide_simulator_protocol::MotorGearset,
+        brake_mode: vexide_simulator_protocol::MotorBrakeMode,
+    },
+}
+
+#[cfg(feature = "codec")]
+#[derive(Debug, PartialEq, serde::Deserialize)]
+enum BaselineEvent {
+    DeviceUpdate {
+        status: BaselineStatus,
+        port: vexide_simulator_protocol::Port,
+    },
+}
+
+#[cfg(feature = "codec")]
+#[test]
+fn v1_writer_sends_motor_direction_as_reversed() {
+    use vexide_simulator_protocol::{
+        codec::Writer, DeviceStatus, MotorBrakeMode, MotorDirection, MotorFaults, MotorGearset,
+        Port, SmartPort,
+    };
+
+    let event = Event::DeviceUpdate {
+        status: DeviceStatus::Motor {
+            velocity: 100.0,
+            direction_configured: MotorDirection::Reverse,
+            spinning_reverse: false,
+            power_draw: 1.0,
+            torque_output: 0.5,
+            flags: 0,
+            position: 90.0,
+            target_position: 180.0,
+            voltage: 12.0,
+            gearset: MotorGearset::Green,
+            brake_mode: MotorBrakeMode::Hold,
+            encoder: None,
+            temperature: 40.0,
+            current: 1.5,
+            efficiency: 80.0,
+            faults: MotorFaults::default(),
+        },
+        port: Port::Smart(SmartPort(1)),
+    };
+    let mut writer = Writer::new(Vec::new());
+    writer.set_version(v1::VERSION);
+    writer.write_event(&event).unwrap();
+    let line = String::from_utf8(writer.into_inner()).unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<BaselineEvent>(&line).unwrap(),
+        BaselineEvent::DeviceUpdate {
+            status: BaselineStatus::Motor {
+                velocity: 100.0,
+                reversed: true,
+                power_draw: 1.0,
+                torque_output: 0.5,
+                flags: 0,
+                position: 90.0,
+                target_position: 180.0,
+                voltage: 12.0,
+                gearset: MotorGearset::Green,
+                brake_mode: MotorBrakeMode::Hold,
+            },
+            port: Port::Smart(SmartPort(1)),
+        }
+    );
+    let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+    let motor = &json["DeviceUpdate"]["status"]["Motor"];
+    assert!(motor.get("direction_configured").is_none());
+    assert!(motor.get("temperature").is_none());
+}
+
+#[test]
+fn v1_keeps_statuses_added_later() {
+    use vexide_simulator_protocol::DeviceStatus;
+
+    let status = DeviceStatus::Electromagnet {
+        power: 0.5,
+        holding: true,
+    };
+    let legacy = v1::DeviceStatus::from(status.clone());
+    assert_eq!(
+        serde_json::to_value(&legacy).unwrap(),
+        serde_json::to_value(&status).unwrap()
+    );
+    assert_eq!(
+        serde_json::from_value::<v1::DeviceStatus>(serde_json::to_value(&status).unwrap()).unwrap(),
+        legacy
+    );
+    assert_eq!(DeviceStatus::from(legacy), status);
+}
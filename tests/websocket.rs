@@ -0,0 +1,148 @@
+#![cfg(feature = "websocket")]
+
+use std::{
+    io,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use tungstenite::{Message, WebSocket};
+use vexide_simulator_protocol::{
+    codec::{self, CodecError},
+    transport::{self, Connection},
+    Event,
+};
+
+/// Accepts a connection over the transport, with a plain WebSocket client as the peer.
+fn pair() -> (Connection, WebSocket<TcpStream>) {
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let uri = format!("ws://127.0.0.1:{port}");
+    let server = thread::spawn({
+        let uri = uri.clone();
+        move || transport::accept(&uri).unwrap()
+    });
+    let start = Instant::now();
+    let stream = loop {
+        match TcpStream::connect(("127.0.0.1", port)) {
+            Ok(stream) => break stream,
+            Err(_) if start.elapsed() < Duration::from_secs(5) => {
+                thread::sleep(Duration::from_millis(10));
+            }
+            Err(err) => panic!("{err}"),
+        }
+    };
+    let (peer, _) = tungstenite::client(uri.as_str(), stream).unwrap();
+    (server.join().unwrap(), peer)
+}
+
+fn log(index: usize) -> Event {
+    Event::log_info(format!("message {index}"))
+}
+
+#[test]
+fn pings_are_answered_while_writing() {
+    const COUNT: usize = 2000;
+    let (connection, mut peer) = pair();
+    let Connection {
+        mut reader,
+        mut writer,
+    } = connection;
+
+    let sender = thread::spawn(move || {
+        for index in 0..COUNT {
+            writer.write_event(&log(index)).unwrap();
+            writer.flush().unwrap();
+        }
+        writer
+    });
+    let receiver = thread::spawn(move || {
+        let mut events = Vec::new();
+        while let Some(event) = reader.read_event().unwrap() {
+            events.push(event);
+        }
+        events
+    });
+
+    let mut received = 0;
+    let mut pongs = 0;
+    let mut pings = 0;
+    while received < COUNT || pongs < pings {
+        if pings < 50 && received % 40 == 0 {
+            peer.send(Message::Ping(vec![pings as u8; 8])).unwrap();
+            pings += 1;
+        }
+        match peer.read().unwrap() {
+            Message::Text(text) => {
+                let event: Event = codec::decode(&text).unwrap();
+                assert_eq!(event, log(received));
+                received += 1;
+            }
+            Message::Pong(payload) => {
+                assert_eq!(payload, vec![pongs as u8; 8]);
+                pongs += 1;
+            }
+            message => panic!("unexpected message: {message:?}"),
+        }
+    }
+    assert_eq!(pongs, 50);
+
+    sender.join().unwrap();
+    peer.send(Message::Text(
+        codec::encode(&Event::Ready).unwrap().trim_end().to_string(),
+    ))
+    .unwrap();
+    peer.close(None).unwrap();
+    // Wait for the reply to the close frame.
+    loop {
+        match peer.read() {
+            Ok(_) => {}
+            Err(tungstenite::Error::ConnectionClosed) => break,
+            Err(err) => panic!("{err}"),
+        }
+    }
+    assert_eq!(receiver.join().unwrap(), [Event::Ready]);
+}
+
+#[test]
+fn writer_is_not_blocked_by_waiting_reader() {
+    let (connection, mut peer) = pair();
+    let Connection {
+        mut reader,
+        mut writer,
+    } = connection;
+
+    let receiver = thread::spawn(move || {
+        let result = reader.read_event_until(Instant::now() + Duration::from_millis(500));
+        assert!(
+            matches!(&result, Err(CodecError::Io(err)) if err.kind() == io::ErrorKind::TimedOut),
+            "{result:?}"
+        );
+        reader
+    });
+
+    thread::sleep(Duration::from_millis(50));
+    let start = Instant::now();
+    writer.write_event(&Event::Ready).unwrap();
+    writer.flush().unwrap();
+    assert!(start.elapsed() < Duration::from_millis(250));
+    assert_eq!(
+        peer.read().unwrap(),
+        Message::Text(codec::encode(&Event::Ready).unwrap().trim_end().to_string())
+    );
+
+    // The reader still works after timing out.
+    let mut reader = receiver.join().unwrap();
+    peer.send(Message::Text(
+        codec::encode(&Event::Exited)
+            .unwrap()
+            .trim_end()
+            .to_string(),
+    ))
+    .unwrap();
+    assert_eq!(reader.read_event().unwrap(), Some(Event::Exited));
+}
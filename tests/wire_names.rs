@@ -0,0 +1,1420 @@
+#![cfg(feature = "codec")]
+
+//! Locks down the name of every variant and field as it appears on the wire, so that renaming
+//! a Rust identifier can't silently break implementations in other languages.
+
+use std::{collections::BTreeSet, fmt::Debug, num::NonZeroU16, num::NonZeroU32};
+
+use mint::{Point2, Quaternion, Vector2, Vector3};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use vexide_simulator_protocol::{
+    codec::Compression,
+    recording::Message,
+    scenario::{Assertion, Step},
+    *,
+};
+
+/// Checks that `value` serializes with the variant name `tag`, and that its payload, if it is
+/// an object, has exactly the keys in `fields`. Also checks that the value survives a round
+/// trip.
+#[track_caller]
+fn assert_wire<T>(value: T, tag: &str, fields: &[&str])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let json = serde_json::to_value(&value).unwrap();
+    let (actual_tag, payload) = match &json {
+        Value::String(tag) => (tag.as_str(), None),
+        Value::Object(map) if map.len() == 1 => {
+            let (tag, payload) = map.iter().next().unwrap();
+            (tag.as_str(), Some(payload))
+        }
+        other => panic!("{value:?} is not an externally tagged variant: {other}"),
+    };
+    assert_eq!(actual_tag, tag, "wrong tag for {value:?}");
+
+    let actual_fields: BTreeSet<&str> = match payload {
+        Some(Value::Object(payload)) => payload.keys().map(String::as_str).collect(),
+        _ => BTreeSet::new(),
+    };
+    let expected_fields: BTreeSet<&str> = fields.iter().copied().collect();
+    assert_eq!(actual_fields, expected_fields, "wrong fields for {value:?}");
+
+    assert_eq!(serde_json::from_value::<T>(json).unwrap(), value);
+}
+
+const SMART: SmartPort = SmartPort(1);
+const ADI: AdiPort = AdiPort(1);
+const PORT: Port = Port::Smart(SmartPort(1));
+const ORIGIN: Point2<i32> = Point2 { x: 0, y: 0 };
+
+fn rect() -> Rect {
+    Rect {
+        top_left: ORIGIN,
+        bottom_right: ORIGIN,
+    }
+}
+
+fn pixel() -> DrawCommand {
+    DrawCommand::Fill {
+        shape: Shape::Pixel { pos: ORIGIN },
+    }
+}
+
+fn event_cases() -> Vec<(Event, &'static str, &'static [&'static str])> {
+    vec![
+        (
+            Event::Handshake {
+                version: 1,
+                extensions: Vec::new(),
+            },
+            "Handshake",
+            &["version", "extensions"],
+        ),
+        (
+            Event::ScreenDraw {
+                command: pixel(),
+                color: Color(0),
+                clip_region: rect(),
+            },
+            "ScreenDraw",
+            &["command", "color", "clip_region"],
+        ),
+        (
+            Event::ScreenScroll {
+                location: ScrollLocation::Line { line: 0 },
+                lines: 1,
+                background: Color(0),
+                clip_region: rect(),
+            },
+            "ScreenScroll",
+            &["location", "lines", "background", "clip_region"],
+        ),
+        (
+            Event::ScreenClear {
+                color: Color(0),
+                clip_region: rect(),
+            },
+            "ScreenClear",
+            &["color", "clip_region"],
+        ),
+        (
+            Event::ScreenDoubleBufferMode { enable: true },
+            "ScreenDoubleBufferMode",
+            &["enable"],
+        ),
+        (Event::ScreenRender, "ScreenRender", &[]),
+        (Event::VCodeSig(VCodeSig::new(b"sig")), "VCodeSig", &[]),
+        (Event::Ready, "Ready", &[]),
+        (Event::Exited, "Exited", &[]),
+        (
+            Event::Serial(SerialData::new(1, b"")),
+            "Serial",
+            &["channel", "data"],
+        ),
+        (
+            Event::DeviceUpdate {
+                status: DeviceStatus::AdiEncoder { ticks: 0 },
+                port: PORT,
+            },
+            "DeviceUpdate",
+            &["status", "port"],
+        ),
+        (
+            Event::DeviceLed {
+                port: SMART,
+                led: LedState::Distance { lit: true },
+            },
+            "DeviceLed",
+            &["port", "led"],
+        ),
+        (
+            Event::Battery(Battery {
+                voltage: 12.0,
+                current: 1.0,
+                capacity: 1.0,
+                details: None,
+            }),
+            "Battery",
+            &["voltage", "current", "capacity", "details"],
+        ),
+        (
+            Event::RobotPose { x: 0.0, y: 0.0 },
+            "RobotPose",
+            &["x", "y"],
+        ),
+        (
+            serde_json::from_str(r#"{"RobotState":null}"#).unwrap(),
+            "RobotState",
+            &[],
+        ),
+        (
+            Event::log_info("hello"),
+            "Log",
+            &[
+                "level", "message", "source", "category", "color", "location", "count",
+            ],
+        ),
+        (
+            Event::VEXLinkConnect {
+                port: SMART,
+                id: "link".to_string(),
+                mode: LinkMode::Manager,
+                r#override: false,
+            },
+            "VEXLinkConnect",
+            &["port", "id", "mode", "override"],
+        ),
+        (
+            Event::VEXLinkDisconnect { port: SMART },
+            "VEXLinkDisconnect",
+            &["port"],
+        ),
+        (
+            Event::TextMetricsRequest {
+                text: V5Text::default(),
+            },
+            "TextMetricsRequest",
+            &["text"],
+        ),
+        (
+            Event::SelectController {
+                id: ControllerId::Primary,
+                uuid: None,
+            },
+            "SelectController",
+            &["id", "uuid"],
+        ),
+        (
+            Event::GpsMount {
+                port: SMART,
+                mount: GpsMount {
+                    offset: Point2 { x: 0.0, y: 0.0 },
+                    heading_offset: 0.0,
+                },
+            },
+            "GpsMount",
+            &["port", "mount"],
+        ),
+        (
+            Event::VisionStream {
+                port: SMART,
+                config: None,
+            },
+            "VisionStream",
+            &["port", "config"],
+        ),
+        (
+            Event::WatchdogExpired {
+                timeout: WatchdogTimeout::Tick,
+            },
+            "WatchdogExpired",
+            &["timeout"],
+        ),
+        (
+            Event::LegalityReport(LegalityReport::default()),
+            "LegalityReport",
+            &[
+                "motor_count",
+                "total_motor_power",
+                "device_count",
+                "warnings",
+            ],
+        ),
+        (
+            Event::Screenshot {
+                trigger: ScreenshotTrigger::Exit,
+                screenshot: Screenshot::new(0, 0, &[]),
+            },
+            "Screenshot",
+            &["trigger", "screenshot"],
+        ),
+        (
+            Event::DevicesConfigured(ConfigureDevicesResult::Applied),
+            "DevicesConfigured",
+            &[],
+        ),
+        (
+            Event::RunSummary(RunSummary::default()),
+            "RunSummary",
+            &[
+                "simulated_ms",
+                "error_count",
+                "warning_count",
+                "panic_count",
+                "assertions",
+                "max_motor_temperatures",
+            ],
+        ),
+        (Event::ProgramSlots(Vec::new()), "ProgramSlots", &[]),
+        (
+            Event::Fidelity(Fidelity::default()),
+            "Fidelity",
+            &["motor_physics", "imu_noise", "timing"],
+        ),
+        (Event::Sync { seq: 0 }, "Sync", &["seq"]),
+        (
+            Event::Notification(Notification {
+                title: String::new(),
+                body: String::new(),
+                severity: LogLevel::Info,
+            }),
+            "Notification",
+            &["title", "body", "severity"],
+        ),
+        (Event::AutonRoutines(Vec::new()), "AutonRoutines", &[]),
+        (
+            Event::AdiOutput {
+                port: Port::Adi(ADI),
+                high: true,
+            },
+            "AdiOutput",
+            &["port", "high"],
+        ),
+        (
+            Event::DeviceDelta {
+                port: PORT,
+                delta: StatusDelta::default(),
+            },
+            "DeviceDelta",
+            &["port", "delta"],
+        ),
+        (
+            Event::AdiLedBuffer {
+                port: Port::Adi(ADI),
+                colors: Vec::new(),
+            },
+            "AdiLedBuffer",
+            &["port", "colors"],
+        ),
+        (
+            Event::TouchListening(TouchListening::Polling),
+            "TouchListening",
+            &[],
+        ),
+        (
+            Event::AdiPwmOutput {
+                port: Port::Adi(ADI),
+                value: 0,
+            },
+            "AdiPwmOutput",
+            &["port", "value"],
+        ),
+        (
+            Event::PortClaimed {
+                port: PORT,
+                kind: "Motor".to_string(),
+            },
+            "PortClaimed",
+            &["port", "kind"],
+        ),
+        (
+            Event::AdiAnalogOutput {
+                port: Port::Adi(ADI),
+                voltage: 0.0,
+            },
+            "AdiAnalogOutput",
+            &["port", "voltage"],
+        ),
+        (
+            Event::DisplayOpened(ExtraDisplay {
+                id: 1,
+                name: String::new(),
+                width: 1,
+                height: 1,
+            }),
+            "DisplayOpened",
+            &["id", "name", "width", "height"],
+        ),
+        (
+            Event::DisplayClosed { display: 1 },
+            "DisplayClosed",
+            &["display"],
+        ),
+        (
+            Event::DisplayDraw {
+                display: 1,
+                command: pixel(),
+                color: Color(0),
+                clip_region: rect(),
+            },
+            "DisplayDraw",
+            &["display", "command", "color", "clip_region"],
+        ),
+        (
+            Event::DisplayClear {
+                display: 1,
+                color: Color(0),
+                clip_region: rect(),
+            },
+            "DisplayClear",
+            &["display", "color", "clip_region"],
+        ),
+        (
+            Event::DisplayRender { display: 1 },
+            "DisplayRender",
+            &["display"],
+        ),
+        (
+            Event::PortSerial(PortSerialData::new(SMART, b"")),
+            "PortSerial",
+            &["port", "data"],
+        ),
+        (
+            Event::HandshakeRejected(HandshakeRejection::MissingExtensions {
+                extensions: Vec::new(),
+            }),
+            "HandshakeRejected",
+            &["MissingExtensions"],
+        ),
+        (
+            Event::MotorLimits {
+                port: SMART,
+                limits: MotorLimits::default(),
+            },
+            "MotorLimits",
+            &["port", "limits"],
+        ),
+        (
+            Event::MotorTelemetry {
+                port: SMART,
+                samples: Vec::new(),
+            },
+            "MotorTelemetry",
+            &["port", "samples"],
+        ),
+    ]
+}
+
+fn command_cases() -> Vec<(Command, &'static str, &'static [&'static str])> {
+    vec![
+        (
+            Command::Handshake {
+                version: 1,
+                extensions: Vec::new(),
+                frame_rate: None,
+            },
+            "Handshake",
+            &["version", "extensions", "frame_rate"],
+        ),
+        (Command::touch_press(0, 0), "Touch", &["pos", "event"]),
+        (
+            Command::ControllerUpdate(None, None),
+            "ControllerUpdate",
+            &[],
+        ),
+        (Command::USD { root: None }, "USD", &["root"]),
+        (
+            Command::VEXLinkOpened {
+                port: SMART,
+                mode: LinkMode::Worker,
+            },
+            "VEXLinkOpened",
+            &["port", "mode"],
+        ),
+        (
+            Command::VEXLinkClosed { port: SMART },
+            "VEXLinkClosed",
+            &["port"],
+        ),
+        (
+            Command::CompetitionMode(CompetitionMode {
+                enabled: true,
+                connected: true,
+                mode: CompMode::Driver,
+                is_competition: false,
+            }),
+            "CompetitionMode",
+            &["enabled", "connected", "mode", "is_competition"],
+        ),
+        (
+            Command::CompetitionSchedule(Vec::new()),
+            "CompetitionSchedule",
+            &[],
+        ),
+        (
+            Command::ConfigureDevice {
+                port: PORT,
+                device: Device::AdiServo,
+            },
+            "ConfigureDevice",
+            &["port", "device"],
+        ),
+        (
+            Command::AdiInput {
+                port: ADI,
+                voltage: 0.0,
+                expander: None,
+            },
+            "AdiInput",
+            &["port", "voltage", "expander"],
+        ),
+        (Command::StartExecution, "StartExecution", &[]),
+        (
+            Command::SetBatteryCapacity { capacity: 1.0 },
+            "SetBatteryCapacity",
+            &["capacity"],
+        ),
+        (
+            Command::SetTextMetrics {
+                text: V5Text::default(),
+                metrics: TextMetrics::default(),
+            },
+            "SetTextMetrics",
+            &["text", "metrics"],
+        ),
+        (
+            Command::Serial(SerialData::new(1, b"")),
+            "Serial",
+            &["channel", "data"],
+        ),
+        (
+            Command::SetSystemTime { unix_ms: 0 },
+            "SetSystemTime",
+            &["unix_ms"],
+        ),
+        (
+            Command::AvailableControllers(Vec::new()),
+            "AvailableControllers",
+            &[],
+        ),
+        (
+            Command::SetGpsField(GpsField {
+                center: Point2 { x: 0.0, y: 0.0 },
+                rotation: 0.0,
+            }),
+            "SetGpsField",
+            &["center", "rotation"],
+        ),
+        (
+            Command::VisionFrame {
+                port: SMART,
+                frame: VisionFrame {
+                    width: 1,
+                    height: 1,
+                    format: FrameFormat::Png,
+                    data: String::new(),
+                },
+            },
+            "VisionFrame",
+            &["port", "frame"],
+        ),
+        (
+            Command::SetEncoderPosition {
+                port: SMART,
+                position: 0.0,
+            },
+            "SetEncoderPosition",
+            &["port", "position"],
+        ),
+        (
+            Command::ConfigureWatchdog(WatchdogConfig::default()),
+            "ConfigureWatchdog",
+            &["max_tick_ms", "max_run_ms"],
+        ),
+        (
+            Command::LegalityReport(LegalityReport::default()),
+            "LegalityReport",
+            &[
+                "motor_count",
+                "total_motor_power",
+                "device_count",
+                "warnings",
+            ],
+        ),
+        (
+            Command::SetSerialPath {
+                path: SerialPath::Direct,
+            },
+            "SetSerialPath",
+            &["path"],
+        ),
+        (
+            Command::SetScreenshotTriggers {
+                triggers: Vec::new(),
+            },
+            "SetScreenshotTriggers",
+            &["triggers"],
+        ),
+        (
+            Command::SetDeviceLatency {
+                port: PORT,
+                micros: 0,
+            },
+            "SetDeviceLatency",
+            &["port", "micros"],
+        ),
+        (
+            Command::ConfigureDevices(Vec::new()),
+            "ConfigureDevices",
+            &[],
+        ),
+        (
+            Command::SelectProgram { slot: 1 },
+            "SelectProgram",
+            &["slot"],
+        ),
+        (Command::RunProgram { slot: 1 }, "RunProgram", &["slot"]),
+        (Command::StopProgram, "StopProgram", &[]),
+        (Command::Sync { seq: 0 }, "Sync", &["seq"]),
+        (
+            Command::SetInputProcessing {
+                controller: ControllerId::Partner,
+                processing: InputProcessing::default(),
+            },
+            "SetInputProcessing",
+            &["controller", "processing"],
+        ),
+        (
+            Command::FrameStats(FrameStats::default()),
+            "FrameStats",
+            &[
+                "frames",
+                "dropped_frames",
+                "mean_latency_ms",
+                "max_latency_ms",
+            ],
+        ),
+        (
+            Command::SelectAutonRoutine { id: String::new() },
+            "SelectAutonRoutine",
+            &["id"],
+        ),
+        (
+            Command::DistanceInput {
+                port: SMART,
+                distance_mm: None,
+                object_size: 0,
+            },
+            "DistanceInput",
+            &["port", "distance_mm", "object_size"],
+        ),
+        (
+            Command::ApplyDisturbance {
+                force: Vector2 { x: 0.0, y: 0.0 },
+                torque: 0.0,
+                duration_ms: 0,
+            },
+            "ApplyDisturbance",
+            &["force", "torque", "duration_ms"],
+        ),
+        (
+            Command::SetValueGenerator {
+                port: PORT,
+                channel: "voltage".to_string(),
+                generator: None,
+            },
+            "SetValueGenerator",
+            &["port", "channel", "generator"],
+        ),
+        (
+            Command::VisionDetections {
+                port: SMART,
+                objects: Vec::new(),
+            },
+            "VisionDetections",
+            &["port", "objects"],
+        ),
+        (
+            Command::InjectFault {
+                fault: Fault::RadioDrop,
+                duration_ms: None,
+            },
+            "InjectFault",
+            &["fault", "duration_ms"],
+        ),
+        (
+            Command::GpsInput {
+                port: SMART,
+                pose: GpsPose {
+                    position: Point2 { x: 0.0, y: 0.0 },
+                    heading: 0.0,
+                },
+                quality: 100,
+                diagnostics: None,
+            },
+            "GpsInput",
+            &["port", "pose", "quality", "diagnostics"],
+        ),
+        (
+            Command::AdvanceAdiEncoder {
+                port: Port::Adi(ADI),
+                ticks: 0,
+            },
+            "AdvanceAdiEncoder",
+            &["port", "ticks"],
+        ),
+        (
+            Command::UltrasonicInput {
+                port: Port::Adi(ADI),
+                distance_mm: None,
+            },
+            "UltrasonicInput",
+            &["port", "distance_mm"],
+        ),
+        (
+            Command::LineTrackerInput {
+                port: Port::Adi(ADI),
+                reflectivity: 0.0,
+            },
+            "LineTrackerInput",
+            &["port", "reflectivity"],
+        ),
+        (
+            Command::AccelerometerInput {
+                port: Port::Adi(ADI),
+                acceleration: Vector3 {
+                    x: 0.0,
+                    y: 0.0,
+                    z: 0.0,
+                },
+            },
+            "AccelerometerInput",
+            &["port", "acceleration"],
+        ),
+        (
+            Command::PortSerial(PortSerialData::new(SMART, b"")),
+            "PortSerial",
+            &["port", "data"],
+        ),
+        (
+            Command::SetAnalogFilter {
+                port: Port::Adi(ADI),
+                filter: AnalogFilter::default(),
+            },
+            "SetAnalogFilter",
+            &["port", "filter"],
+        ),
+        (
+            Command::HandshakeRejected(HandshakeRejection::RoleConflict {
+                retry_after_ms: None,
+            }),
+            "HandshakeRejected",
+            &["RoleConflict"],
+        ),
+        (
+            Command::SetMotorTelemetry {
+                port: SMART,
+                decimation: NonZeroU32::new(1),
+            },
+            "SetMotorTelemetry",
+            &["port", "decimation"],
+        ),
+    ]
+}
+
+#[test]
+fn event_names() {
+    for (event, tag, fields) in event_cases() {
+        assert_eq!(event.kind(), tag);
+        assert_wire(event, tag, fields);
+    }
+}
+
+#[test]
+fn command_names() {
+    for (command, tag, fields) in command_cases() {
+        assert_eq!(command.kind(), tag);
+        assert_wire(command, tag, fields);
+    }
+}
+
+#[test]
+fn every_event_is_covered() {
+    let covered: BTreeSet<&str> = event_cases().iter().map(|(_, tag, _)| *tag).collect();
+    let described: BTreeSet<&str> = Event::describe().iter().map(|v| v.name).collect();
+    assert_eq!(covered, described);
+}
+
+#[test]
+fn every_command_is_covered() {
+    let covered: BTreeSet<&str> = command_cases().iter().map(|(_, tag, _)| *tag).collect();
+    let described: BTreeSet<&str> = Command::describe().iter().map(|v| v.name).collect();
+    assert_eq!(covered, described);
+}
+
+#[test]
+fn device_names() {
+    let cases = [
+        (
+            Device::Motor {
+                physical_gearset: MotorGearset::Green,
+                moment_of_inertia: 0.0,
+                reversed: false,
+                external_ratio: 1.0,
+            },
+            "Motor",
+            &[
+                "physical_gearset",
+                "moment_of_inertia",
+                "reversed",
+                "external_ratio",
+            ][..],
+        ),
+        (
+            Device::Gps {
+                mount: GpsMount {
+                    offset: Point2 { x: 0.0, y: 0.0 },
+                    heading_offset: 0.0,
+                },
+                initial_pose: None,
+            },
+            "Gps",
+            &["mount", "initial_pose"],
+        ),
+        (
+            Device::Imu {
+                orientation: ImuOrientation::ZUp,
+                offset: None,
+                drift: ImuDrift::default(),
+            },
+            "Imu",
+            &["orientation", "offset", "drift"],
+        ),
+        (
+            Device::Distance {
+                field_of_view: 0.0,
+                min_range: 0.0,
+                max_range: 0.0,
+                reflectivity: 0.0,
+            },
+            "Distance",
+            &["field_of_view", "min_range", "max_range", "reflectivity"],
+        ),
+        (
+            Device::RotationSensor { reversed: false },
+            "RotationSensor",
+            &["reversed"],
+        ),
+        (
+            Device::Optical {
+                integration_time_ms: 0.0,
+            },
+            "Optical",
+            &["integration_time_ms"],
+        ),
+        (
+            Device::Vision {
+                horizontal_fov: 0.0,
+                vertical_fov: 0.0,
+            },
+            "Vision",
+            &["horizontal_fov", "vertical_fov"],
+        ),
+        (
+            Device::AiVision {
+                tag_family: None,
+                color_detection: false,
+                model_detection: false,
+            },
+            "AiVision",
+            &["tag_family", "color_detection", "model_detection"],
+        ),
+        (
+            Device::Electromagnet { pickup_range: 0.0 },
+            "Electromagnet",
+            &["pickup_range"],
+        ),
+        (Device::AdiExpander, "AdiExpander", &[]),
+        (Device::AdiDigitalIn, "AdiDigitalIn", &[]),
+        (Device::AdiDigitalOut, "AdiDigitalOut", &[]),
+        (
+            Device::AdiEncoder { bottom: AdiPort(2) },
+            "AdiEncoder",
+            &["bottom"],
+        ),
+        (
+            Device::AdiUltrasonic { echo: AdiPort(2) },
+            "AdiUltrasonic",
+            &["echo"],
+        ),
+        (
+            Device::AdiGyro { multiplier: 1.0 },
+            "AdiGyro",
+            &["multiplier"],
+        ),
+        (
+            Device::AdiPotentiometer {
+                kind: PotentiometerKind::V2,
+            },
+            "AdiPotentiometer",
+            &["kind"],
+        ),
+        (Device::AdiLineTracker, "AdiLineTracker", &[]),
+        (Device::AdiAddrLed { length: 1 }, "AdiAddrLed", &["length"]),
+        (Device::AdiServo, "AdiServo", &[]),
+        (Device::AdiPwmMotor, "AdiPwmMotor", &[]),
+        (Device::AdiAnalogOut, "AdiAnalogOut", &[]),
+        (
+            Device::AdiAccelerometer {
+                y: AdiPort(2),
+                z: AdiPort(3),
+                range: AccelerometerRange::Low,
+            },
+            "AdiAccelerometer",
+            &["y", "z", "range"],
+        ),
+        (Device::AdiSolenoid, "AdiSolenoid", &[]),
+        (
+            Device::GenericSerial { baud: 115_200 },
+            "GenericSerial",
+            &["baud"],
+        ),
+        (Device::Radio, "Radio", &[]),
+    ];
+    for (device, tag, fields) in cases {
+        assert_eq!(device.kind(), tag);
+        assert_wire(device, tag, fields);
+    }
+}
+
+#[test]
+fn device_status_names() {
+    let zero = Vector3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+    let cases = [
+        (
+            DeviceStatus::Motor {
+                velocity: 0.0,
+                direction_configured: MotorDirection::Forward,
+                spinning_reverse: false,
+                power_draw: 0.0,
+                torque_output: 0.0,
+                flags: 0,
+                position: 0.0,
+                target_position: 0.0,
+                voltage: 0.0,
+                gearset: MotorGearset::Green,
+                brake_mode: MotorBrakeMode::Coast,
+                encoder: None,
+                temperature: 0.0,
+                current: 0.0,
+                efficiency: 0.0,
+                faults: MotorFaults::default(),
+            },
+            "Motor",
+            &[
+                "velocity",
+                "direction_configured",
+                "spinning_reverse",
+                "power_draw",
+                "torque_output",
+                "flags",
+                "position",
+                "target_position",
+                "voltage",
+                "gearset",
+                "brake_mode",
+                "encoder",
+                "temperature",
+                "current",
+                "efficiency",
+                "faults",
+            ][..],
+        ),
+        (
+            DeviceStatus::Imu {
+                heading: 0.0,
+                rotation: 0.0,
+                quaternion: Quaternion { v: zero, s: 1.0 },
+                euler: EulerAngles::default(),
+                gyro_rate: zero,
+                acceleration: zero,
+                calibrating: false,
+            },
+            "Imu",
+            &[
+                "heading",
+                "rotation",
+                "quaternion",
+                "euler",
+                "gyro_rate",
+                "acceleration",
+                "calibrating",
+            ],
+        ),
+        (
+            DeviceStatus::RotationSensor {
+                angle: 0.0,
+                position: 0.0,
+                velocity: 0.0,
+                reversed: false,
+                encoder: None,
+            },
+            "RotationSensor",
+            &["angle", "position", "velocity", "reversed", "encoder"],
+        ),
+        (
+            DeviceStatus::Distance {
+                distance_mm: None,
+                confidence: 0,
+                object_size: 0,
+                object_velocity: 0.0,
+            },
+            "Distance",
+            &[
+                "distance_mm",
+                "confidence",
+                "object_size",
+                "object_velocity",
+            ],
+        ),
+        (
+            DeviceStatus::Optical {
+                hue: 0.0,
+                saturation: 0.0,
+                brightness: 0.0,
+                proximity: 0,
+                led_pwm: 0,
+            },
+            "Optical",
+            &["hue", "saturation", "brightness", "proximity", "led_pwm"],
+        ),
+        (
+            DeviceStatus::Vision {
+                objects: Vec::new(),
+            },
+            "Vision",
+            &["objects"],
+        ),
+        (
+            DeviceStatus::AiVision {
+                tags: Vec::new(),
+                blobs: Vec::new(),
+                objects: Vec::new(),
+            },
+            "AiVision",
+            &["tags", "blobs", "objects"],
+        ),
+        (
+            DeviceStatus::Gps {
+                pose: GpsPose {
+                    position: Point2 { x: 0.0, y: 0.0 },
+                    heading: 0.0,
+                },
+                quality: 0,
+                diagnostics: None,
+            },
+            "Gps",
+            &["pose", "quality", "diagnostics"],
+        ),
+        (
+            DeviceStatus::Electromagnet {
+                power: 0.0,
+                holding: false,
+            },
+            "Electromagnet",
+            &["power", "holding"],
+        ),
+        (
+            DeviceStatus::AdiEncoder { ticks: 0 },
+            "AdiEncoder",
+            &["ticks"],
+        ),
+        (
+            DeviceStatus::AdiUltrasonic { distance_mm: None },
+            "AdiUltrasonic",
+            &["distance_mm"],
+        ),
+        (
+            DeviceStatus::AdiGyro {
+                heading: 0.0,
+                calibrating: false,
+            },
+            "AdiGyro",
+            &["heading", "calibrating"],
+        ),
+        (
+            DeviceStatus::AdiPotentiometer { angle: 0.0 },
+            "AdiPotentiometer",
+            &["angle"],
+        ),
+        (
+            DeviceStatus::AdiLineTracker { reflectivity: 0.0 },
+            "AdiLineTracker",
+            &["reflectivity"],
+        ),
+        (
+            DeviceStatus::AdiAccelerometer { acceleration: zero },
+            "AdiAccelerometer",
+            &["acceleration"],
+        ),
+        (
+            DeviceStatus::AdiSolenoid {
+                extended: false,
+                actuations: 0,
+            },
+            "AdiSolenoid",
+            &["extended", "actuations"],
+        ),
+        (
+            DeviceStatus::Radio {
+                connection: RadioConnection::VexNet,
+                channel: None,
+                signal_quality: 0,
+            },
+            "Radio",
+            &["connection", "channel", "signal_quality"],
+        ),
+    ];
+    for (status, tag, fields) in cases {
+        assert_wire(status, tag, fields);
+    }
+}
+
+#[test]
+fn screen_names() {
+    assert_wire(pixel(), "Fill", &["shape"]);
+    assert_wire(
+        DrawCommand::Stroke {
+            shape: Shape::Pixel { pos: ORIGIN },
+        },
+        "Stroke",
+        &["shape"],
+    );
+    assert_wire(
+        DrawCommand::CopyBuffer {
+            top_left: ORIGIN,
+            bottom_right: ORIGIN,
+            stride: NonZeroU16::MIN,
+            buffer: String::new(),
+        },
+        "CopyBuffer",
+        &["top_left", "bottom_right", "stride", "buffer"],
+    );
+    assert_wire(
+        DrawCommand::Write {
+            text: V5Text::default(),
+            location: TextLocation::Line { line: 0 },
+            background: TextBackground::Transparent,
+        },
+        "Write",
+        &["text", "location", "background"],
+    );
+
+    assert_wire(
+        Shape::Rectangle {
+            top_left: ORIGIN,
+            bottom_right: ORIGIN,
+        },
+        "Rectangle",
+        &["top_left", "bottom_right"],
+    );
+    assert_wire(
+        Shape::Circle {
+            center: ORIGIN,
+            radius: 1,
+        },
+        "Circle",
+        &["center", "radius"],
+    );
+    assert_wire(Shape::Pixel { pos: ORIGIN }, "Pixel", &["pos"]);
+    assert_wire(
+        Shape::Line {
+            start: ORIGIN,
+            end: ORIGIN,
+        },
+        "Line",
+        &["start", "end"],
+    );
+
+    assert_wire(TextBackground::Transparent, "Transparent", &[]);
+    assert_wire(TextBackground::Solid(Color(0)), "Solid", &[]);
+    assert_wire(
+        TextLocation::Coordinates { point: ORIGIN },
+        "Coordinates",
+        &["point"],
+    );
+    assert_wire(TextLocation::Line { line: 0 }, "Line", &["line"]);
+    assert_wire(ScrollLocation::Line { line: 0 }, "Line", &["line"]);
+    assert_wire(
+        ScrollLocation::Rectangle {
+            top_left: ORIGIN,
+            bottom_right: ORIGIN,
+        },
+        "Rectangle",
+        &["top_left", "bottom_right"],
+    );
+
+    assert_wire(V5FontFamily::UserMono, "UserMono", &[]);
+    assert_wire(V5FontFamily::TimerMono, "TimerMono", &[]);
+    assert_wire(V5FontSize::Small, "Small", &[]);
+    assert_wire(V5FontSize::Normal, "Normal", &[]);
+    assert_wire(V5FontSize::Large, "Large", &[]);
+    assert_wire(TouchEvent::Released, "Released", &[]);
+    assert_wire(TouchEvent::Pressed, "Pressed", &[]);
+    assert_wire(TouchEvent::Held, "Held", &[]);
+    assert_wire(ScreenshotTrigger::Panic, "Panic", &[]);
+    assert_wire(ScreenshotTrigger::Exit, "Exit", &[]);
+    assert_wire(
+        ScreenshotTrigger::CompetitionModeChange,
+        "CompetitionModeChange",
+        &[],
+    );
+    assert_wire(TouchListening::None, "None", &[]);
+    assert_wire(TouchListening::Polling, "Polling", &[]);
+    assert_wire(TouchListening::Callbacks, "Callbacks", &[]);
+}
+
+#[test]
+fn port_names() {
+    assert_wire(Port::Smart(SMART), "Smart", &[]);
+    assert_wire(Port::Adi(ADI), "Adi", &[]);
+    assert_wire(Port::Internal(InternalDevice::Battery), "Internal", &[]);
+    assert_wire(
+        Port::ExpanderAdi {
+            expander: SMART,
+            port: ADI,
+        },
+        "ExpanderAdi",
+        &["expander", "port"],
+    );
+    assert_wire(InternalDevice::Battery, "Battery", &[]);
+    assert_wire(InternalDevice::Brain, "Brain", &[]);
+    assert_wire(
+        InternalDevice::Controller(ControllerId::Primary),
+        "Controller",
+        &[],
+    );
+}
+
+#[test]
+fn device_enum_names() {
+    assert_wire(
+        LedState::Optical { brightness: 0 },
+        "Optical",
+        &["brightness"],
+    );
+    assert_wire(LedState::Vision { color: Color(0) }, "Vision", &["color"]);
+    assert_wire(LedState::Distance { lit: false }, "Distance", &["lit"]);
+
+    assert_wire(ConfigureDevicesResult::Applied, "Applied", &[]);
+    assert_wire(
+        ConfigureDevicesResult::Rejected { errors: Vec::new() },
+        "Rejected",
+        &["errors"],
+    );
+
+    assert_wire(GpsSolution::Initial, "Initial", &[]);
+    assert_wire(GpsSolution::DeadReckoning, "DeadReckoning", &[]);
+    assert_wire(GpsSolution::Fix, "Fix", &[]);
+    assert_wire(ImuOrientation::ZUp, "ZUp", &[]);
+    assert_wire(ImuOrientation::ZDown, "ZDown", &[]);
+    assert_wire(ImuOrientation::XUp, "XUp", &[]);
+    assert_wire(ImuOrientation::XDown, "XDown", &[]);
+    assert_wire(ImuOrientation::YUp, "YUp", &[]);
+    assert_wire(ImuOrientation::YDown, "YDown", &[]);
+    assert_wire(PotentiometerKind::Legacy, "Legacy", &[]);
+    assert_wire(PotentiometerKind::V2, "V2", &[]);
+    assert_wire(AccelerometerRange::Low, "Low", &[]);
+    assert_wire(AccelerometerRange::High, "High", &[]);
+    assert_wire(RadioConnection::Disconnected, "Disconnected", &[]);
+    assert_wire(RadioConnection::VexNet, "VexNet", &[]);
+    assert_wire(RadioConnection::Bluetooth, "Bluetooth", &[]);
+    assert_wire(RadioConnection::Download, "Download", &[]);
+    assert_wire(MotorGearset::Red, "Red", &[]);
+    assert_wire(MotorGearset::Green, "Green", &[]);
+    assert_wire(MotorGearset::Blue, "Blue", &[]);
+    assert_wire(MotorDirection::Forward, "Forward", &[]);
+    assert_wire(MotorDirection::Reverse, "Reverse", &[]);
+    assert_wire(MotorBrakeMode::Coast, "Coast", &[]);
+    assert_wire(MotorBrakeMode::Brake, "Brake", &[]);
+    assert_wire(MotorBrakeMode::Hold, "Hold", &[]);
+    assert_wire(ChargeState::Discharging, "Discharging", &[]);
+    assert_wire(ChargeState::Charging, "Charging", &[]);
+    assert_wire(ChargeState::Full, "Full", &[]);
+
+    assert_wire(FrameFormat::Rgb8, "Rgb8", &[]);
+    assert_wire(FrameFormat::Png, "Png", &[]);
+    assert_wire(FrameFormat::Jpeg, "Jpeg", &[]);
+    assert_wire(AprilTagFamily::Tag16h5, "Tag16h5", &[]);
+    assert_wire(AprilTagFamily::Tag21h7, "Tag21h7", &[]);
+    assert_wire(AprilTagFamily::Tag25h9, "Tag25h9", &[]);
+    assert_wire(AprilTagFamily::Tag36h11, "Tag36h11", &[]);
+
+    assert_wire(
+        ValueGenerator::Constant { value: 0.0 },
+        "Constant",
+        &["value"],
+    );
+    assert_wire(
+        ValueGenerator::Ramp {
+            start: 0.0,
+            end: 1.0,
+            duration_ms: 1,
+        },
+        "Ramp",
+        &["start", "end", "duration_ms"],
+    );
+    assert_wire(
+        ValueGenerator::Sine {
+            offset: 0.0,
+            amplitude: 1.0,
+            period_ms: 1,
+        },
+        "Sine",
+        &["offset", "amplitude", "period_ms"],
+    );
+    assert_wire(
+        ValueGenerator::Samples {
+            interval_ms: 1,
+            values: Vec::new(),
+            repeat: false,
+        },
+        "Samples",
+        &["interval_ms", "values", "repeat"],
+    );
+}
+
+#[test]
+fn controller_names() {
+    assert_wire(
+        ControllerUpdate::Raw(ControllerState::default()),
+        "Raw",
+        &[
+            "axis1",
+            "axis2",
+            "axis3",
+            "axis4",
+            "button_l1",
+            "button_l2",
+            "button_r1",
+            "button_r2",
+            "button_up",
+            "button_down",
+            "button_left",
+            "button_right",
+            "button_x",
+            "button_b",
+            "button_y",
+            "button_a",
+            "button_sel",
+            "battery_level",
+            "button_all",
+            "flags",
+            "battery_capacity",
+        ],
+    );
+    assert_wire(ControllerUpdate::UUID(String::new()), "UUID", &[]);
+    assert_wire(ControllerId::Primary, "Primary", &[]);
+    assert_wire(ControllerId::Partner, "Partner", &[]);
+    assert_wire(PhysicalControllerKind::V5Controller, "V5Controller", &[]);
+    assert_wire(PhysicalControllerKind::Gamepad, "Gamepad", &[]);
+    assert_wire(PhysicalControllerKind::Joystick, "Joystick", &[]);
+    assert_wire(PhysicalControllerKind::Other, "Other", &[]);
+    assert_wire(AxisCurve::Linear, "Linear", &[]);
+    assert_wire(AxisCurve::Power { exponent: 2.0 }, "Power", &["exponent"]);
+}
+
+#[test]
+fn session_enum_names() {
+    assert_wire(LogSource::Simulator, "Simulator", &[]);
+    assert_wire(LogSource::Runtime, "Runtime", &[]);
+    assert_wire(LogSource::User, "User", &[]);
+    assert_wire(LogLevel::Trace, "Trace", &[]);
+    assert_wire(LogLevel::Info, "Info", &[]);
+    assert_wire(LogLevel::Warn, "Warn", &[]);
+    assert_wire(LogLevel::Error, "Error", &[]);
+
+    assert_wire(
+        HandshakeRejection::UnsupportedVersion {
+            offered: 2,
+            supported: vec![1],
+        },
+        "UnsupportedVersion",
+        &["offered", "supported"],
+    );
+    assert_wire(
+        HandshakeRejection::MissingExtensions {
+            extensions: Vec::new(),
+        },
+        "MissingExtensions",
+        &["extensions"],
+    );
+    assert_wire(
+        HandshakeRejection::RoleConflict {
+            retry_after_ms: None,
+        },
+        "RoleConflict",
+        &["retry_after_ms"],
+    );
+
+    assert_wire(
+        Fault::DeviceTimeout { port: PORT },
+        "DeviceTimeout",
+        &["port"],
+    );
+    assert_wire(Fault::RadioDrop, "RadioDrop", &[]);
+    assert_wire(Fault::BatteryDisconnect, "BatteryDisconnect", &[]);
+    assert_wire(Fault::SdError, "SdError", &[]);
+    assert_wire(Fault::ScreenFreeze, "ScreenFreeze", &[]);
+    assert_wire(WatchdogTimeout::Tick, "Tick", &[]);
+    assert_wire(WatchdogTimeout::Run, "Run", &[]);
+
+    assert_wire(CompMode::Auto, "Auto", &[]);
+    assert_wire(CompMode::Driver, "Driver", &[]);
+    assert_wire(LinkMode::Manager, "Manager", &[]);
+    assert_wire(LinkMode::Worker, "Worker", &[]);
+    assert_wire(SerialPath::Direct, "Direct", &[]);
+    assert_wire(SerialPath::Controller, "Controller", &[]);
+    assert_wire(MotorPhysics::None, "None", &[]);
+    assert_wire(MotorPhysics::Kinematic, "Kinematic", &[]);
+    assert_wire(MotorPhysics::Dynamic, "Dynamic", &[]);
+    assert_wire(TimingFidelity::Unpaced, "Unpaced", &[]);
+    assert_wire(TimingFidelity::RealTime, "RealTime", &[]);
+    assert_wire(TimingFidelity::Deterministic, "Deterministic", &[]);
+    assert_wire(Compression::None, "None", &[]);
+    assert_wire(Compression::Zstd, "Zstd", &[]);
+}
+
+#[test]
+fn file_format_names() {
+    assert_wire(Message::Event(Event::Ready), "Event", &[]);
+    assert_wire(Message::Command(Command::StartExecution), "Command", &[]);
+
+    assert_wire(Step::Send(Command::StartExecution), "Send", &[]);
+    assert_wire(Step::Wait { ms: 0 }, "Wait", &["ms"]);
+    assert_wire(
+        Step::Expect {
+            assertion: Assertion::Exited,
+            timeout_ms: 0,
+        },
+        "Expect",
+        &["assertion", "timeout_ms"],
+    );
+    assert_wire(
+        Assertion::Kind {
+            kind: "Ready".to_string(),
+        },
+        "Kind",
+        &["kind"],
+    );
+    assert_wire(
+        Assertion::Log {
+            contains: String::new(),
+        },
+        "Log",
+        &["contains"],
+    );
+    assert_wire(
+        Assertion::Pose {
+            x: 0.0,
+            y: 0.0,
+            tolerance: 0.0,
+        },
+        "Pose",
+        &["x", "y", "tolerance"],
+    );
+    assert_wire(Assertion::Exited, "Exited", &[]);
+    assert_wire(
+        Assertion::Fidelity {
+            minimum: Fidelity::default(),
+        },
+        "Fidelity",
+        &["minimum"],
+    );
+}